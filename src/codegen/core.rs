@@ -1,4 +1,5 @@
 use crate::common::Result;
+use crate::parser::data_type::DataType;
 
 pub trait RegLoadable {
     fn to_arm(&self) -> String;
@@ -6,18 +7,101 @@ pub trait RegLoadable {
     fn to_gnu_x86(&self) -> String;
 }
 
+/**
+ Relational operators, evaluated by `cg_compare` into 0 / 1
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CompareOp {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    UnsignedLess,
+    UnsignedLessEqual,
+    UnsignedGreater,
+    UnsignedGreaterEqual,
+}
+
 pub trait CodeGen {
 
     fn cg_pre_amble(&mut self) -> Result<()>;
 
     fn cg_post_amble(&mut self) -> Result<()>;
 
+    /**
+    Starts the definition of function `name`, its code is generated until `cg_function_postamble`
+    */
+    fn cg_function_preamble(&mut self, name: &str) -> Result<()>;
+
+    /**
+    Makes the parameters of the current function addressable, returns their frame offsets
+    */
+    fn cg_declare_parameters(&mut self, param_types: &[DataType]) -> Result<Vec<i64>>;
+
+    fn cg_function_postamble(&mut self) -> Result<()>;
+
+    /**
+    Returns from the current function, with the value of `reg` if there is one
+    */
+    fn cg_return(&mut self, reg: Option<usize>) -> Result<()>;
+
+    /**
+    Saves live registers and aligns the stack for a call with arguments of `arg_types`.
+    Returns the order in which the arguments have to be evaluated and pushed with `cg_push`
+    before `cg_call`
+    */
+    fn cg_call_prepare(&mut self, arg_types: &[DataType]) -> Result<Vec<usize>>;
+
+    fn cg_call(&mut self, name: &str, return_type: DataType) -> Result<usize>;
+
     fn cg_push(&mut self, reg: usize) -> Result<()>;
 
     fn cg_pop(&mut self, reg: usize) -> Result<()>;
 
+    fn cg_free_register(&mut self, reg: usize);
+
+    /**
+    Whether a value held while evaluating another subexpression should be spilled first,
+    so that the subexpression is guaranteed enough registers
+    */
+    fn cg_registers_low(&self) -> bool;
+
+    /**
+    Stores `reg` into a stack slot and frees it, returns the slot
+    */
+    fn cg_spill(&mut self, reg: usize) -> Result<usize>;
+
+    /**
+    Loads a spilled value back into a newly allocated register and releases its slot
+    */
+    fn cg_reload(&mut self, slot: usize) -> Result<usize>;
+
     fn cg_load<T: RegLoadable>(&mut self, value: T) -> Result<usize>;
 
+    /**
+    Loads a floating constant of type `data_type`, float or double
+    */
+    fn cg_load_float(&mut self, value: f64, data_type: DataType) -> Result<usize>;
+
+    /**
+    Loads the address of a read-only copy of `value`, terminated by a NUL byte
+    */
+    fn cg_load_string(&mut self, value: &[u8]) -> Result<usize>;
+
+    /**
+    Reserves a stack slot in the current frame for a local variable, returns its frame offset.
+    */
+    fn cg_declare_local(&mut self, data_type: DataType) -> Result<i64>;
+
+    fn cg_load_local(&mut self, offset: i64, data_type: DataType) -> Result<usize>;
+
+    /**
+    Stores `reg` into the local variable at `offset`, the register keeps holding the value.
+    */
+    fn cg_store_local(&mut self, reg: usize, offset: i64, data_type: DataType) -> Result<usize>;
+
     fn cg_add(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
 
     fn cg_sub(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
@@ -26,6 +110,105 @@ pub trait CodeGen {
 
     fn cg_div(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
 
-    fn cg_printreg(&mut self, reg: usize) -> Result<()>;
+    fn cg_udiv(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
+
+    /**
+    Remainder of the signed division `reg1 / reg2`
+    */
+    fn cg_mod(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
+
+    fn cg_umod(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
+
+    fn cg_shl(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
+
+    /**
+    Arithmetic (sign preserving) right shift
+    */
+    fn cg_shr(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
+
+    /**
+    Logical (zero filling) right shift
+    */
+    fn cg_ushr(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
+
+    fn cg_and(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
+
+    fn cg_or(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
 
-}
\ No newline at end of file
+    fn cg_xor(&mut self, reg1: usize, reg2: usize) -> Result<usize>;
+
+    /**
+    Evaluates `reg1 <op> reg2`, leaving 1 in the result register if it holds and 0 otherwise.
+    */
+    fn cg_compare(&mut self, reg1: usize, reg2: usize, op: CompareOp) -> Result<usize>;
+
+    // floating point arithmetic on two values of `data_type`, the result is left in `reg1`
+
+    fn cg_fadd(&mut self, reg1: usize, reg2: usize, data_type: DataType) -> Result<usize>;
+
+    fn cg_fsub(&mut self, reg1: usize, reg2: usize, data_type: DataType) -> Result<usize>;
+
+    fn cg_fmul(&mut self, reg1: usize, reg2: usize, data_type: DataType) -> Result<usize>;
+
+    fn cg_fdiv(&mut self, reg1: usize, reg2: usize, data_type: DataType) -> Result<usize>;
+
+    /**
+    Compares two floating values like `cg_compare`, the 0 / 1 result is an integer.
+    Every comparison but `!=` is false when an operand is NaN
+    */
+    fn cg_fcompare(&mut self, reg1: usize, reg2: usize, op: CompareOp, data_type: DataType) -> Result<usize>;
+
+    fn cg_fneg(&mut self, reg: usize, data_type: DataType) -> Result<usize>;
+
+    /**
+    Converts the value in `reg` from type `from` to type `to`, the result may be held in another
+    register when the conversion is between integer and floating types
+    */
+    fn cg_cast(&mut self, reg: usize, from: DataType, to: DataType) -> Result<usize>;
+
+    /**
+    Wraps a result computed on the full register around to the range of `data_type`
+    */
+    fn cg_wrap(&mut self, reg: usize, data_type: DataType) -> Result<usize>;
+
+    fn cg_neg(&mut self, reg: usize) -> Result<usize>;
+
+    fn cg_bitnot(&mut self, reg: usize) -> Result<usize>;
+
+    /**
+    Normalizes `reg` into 0 / 1, i.e. `reg != 0`
+    */
+    fn cg_to_bool(&mut self, reg: usize) -> Result<usize>;
+
+    /**
+    Logical negation, i.e. `reg == 0`
+    */
+    fn cg_not(&mut self, reg: usize) -> Result<usize>;
+
+    /**
+    Copies `src` into `dst` and frees `src`, `dst` is allocated if it was free
+    */
+    fn cg_move(&mut self, src: usize, dst: usize) -> Result<usize>;
+
+    fn cg_new_label(&mut self) -> usize;
+
+    fn cg_label(&mut self, label: usize) -> Result<()>;
+
+    fn cg_jump(&mut self, label: usize) -> Result<()>;
+
+    /**
+    Jumps to `label` if `reg` holds zero, `reg` stays allocated
+    */
+    fn cg_jump_if_zero(&mut self, reg: usize, label: usize) -> Result<()>;
+
+    /**
+    Jumps to `label` if `reg` holds a non-zero value, `reg` stays allocated
+    */
+    fn cg_jump_if_not_zero(&mut self, reg: usize, label: usize) -> Result<()>;
+
+    /**
+    Prints the value of `reg`, formatted according to its promoted type
+    */
+    fn cg_printreg(&mut self, reg: usize, data_type: DataType) -> Result<()>;
+
+}
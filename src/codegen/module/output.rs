@@ -1,11 +1,19 @@
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::Path;
 use crate::common::errors::error::CompilerErrorKind;
 use crate::common::Result;
 
+/**
+ Where the compiler writes its output: a file, or stdout
+ */
 pub struct FileOutput {
 
-    file: File,
+    file: Box<dyn Write>,
+    /**
+    Set once the reader of a pipe has gone, as `head` does: the rest is dropped without an error
+    */
+    closed: bool,
 
 }
 
@@ -20,26 +28,137 @@ macro_rules! file_writeln {
 
 impl FileOutput {
 
-    pub fn new(path: &str) -> Result<FileOutput> {
+    pub fn new(path: &Path) -> Result<FileOutput> {
         match File::options().write(true).create(true).truncate(true).open(path) {
             Ok(file) => {
                 Ok(Self {
-                    file,
+                    file: Box::new(BufWriter::new(file)),
+                    closed: false,
                 })
             },
             Err(e) => {
-                Err(CompilerErrorKind::FileError(e.to_string()).into())
+                Err(CompilerErrorKind::FileError(format!("Cannot write '{}': {}", path.display(), e)))
             }
 
         }
     }
 
+    pub fn stdout() -> FileOutput {
+        Self {
+            file: Box::new(std::io::stdout()),
+            closed: false,
+        }
+    }
+
     pub fn writeln(&mut self, line: &str) -> Result<()> {
-        let line = format!("{}\n", line);
-        match self.file.write_all(line.as_bytes()) {
+        self.write(&format!("{}\n", line))
+    }
+
+    pub fn write(&mut self, text: &str) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        let result = self.file.write_all(text.as_bytes());
+        self.check(result)
+    }
+
+    /**
+    Writes out what is buffered, errors would go unnoticed when the output is dropped
+    */
+    pub fn flush(&mut self) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        let result = self.file.flush();
+        self.check(result)
+    }
+
+    fn check(&mut self, result: std::io::Result<()>) -> Result<()> {
+        match result {
             Ok(_) => Ok(()),
-            Err(e) => Err(CompilerErrorKind::FileError(e.to_string()).into()),
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => {
+                self.closed = true;
+                Ok(())
+            },
+            Err(e) => Err(CompilerErrorKind::FileError(e.to_string())),
+        }
+    }
+
+}
+
+/**
+ <p> In-memory line buffer. </p>
+
+ Code that depends on information only known later (e.g. the stack frame size in a
+ function prologue) is collected here first and flushed into a `FileOutput` afterwards.
+ */
+pub struct TextBuffer {
+
+    lines: Vec<String>,
+
+}
+
+impl TextBuffer {
+
+    pub fn new() -> TextBuffer {
+        Self { lines: Vec::new() }
+    }
+
+    pub fn writeln(&mut self, line: &str) -> Result<()> {
+        self.lines.push(line.to_string());
+        Ok(())
+    }
+
+    pub fn flush_into(&mut self, output: &mut FileOutput) -> Result<()> {
+        for line in self.lines.drain(..) {
+            output.writeln(&line)?;
         }
+        Ok(())
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+    A pipe whose reader has gone after `left` bytes
+    */
+    struct Pipe {
+        left: usize,
+    }
+
+    impl Write for Pipe {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.left == 0 {
+                return Err(ErrorKind::BrokenPipe.into());
+            }
+            let written = buf.len().min(self.left);
+            self.left -= written;
+            Ok(written)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_broken_pipe() {
+        let mut output = FileOutput {
+            file: Box::new(Pipe { left: 4 }),
+            closed: false,
+        };
+        assert!(output.writeln("main:").is_ok());
+        assert!(output.writeln("\tret").is_ok());
+        assert!(output.flush().is_ok());
+        assert!(output.closed);
+
+        let mut output = FileOutput {
+            file: Box::new(std::fs::File::open("/dev/null").unwrap()),
+            closed: false,
+        };
+        assert!(matches!(output.write("read only"), Err(CompilerErrorKind::FileError(_))));
+    }
+}
@@ -6,33 +6,70 @@ pub enum RegistersType {
     ARM,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RegisterClass {
+    General,
+    /**
+    Registers holding floating point values
+    */
+    Float,
+}
+
+pub struct RegisterInfo {
+    /**
+    Register names by operand size: 8, 4, 2 and 1 byte(s)
+    */
+    names: [&'static str; 4],
+    class: RegisterClass,
+    /**
+    Whether a callee has to preserve the register across a call
+    */
+    callee_saved: bool,
+}
+
 pub struct Registers {
-    avail: [bool; 4],
-    name: [&'static str; 4],
+    avail: Vec<bool>,
+    /**
+    Registers handed out at least once since `init_registers`
+    */
+    used: Vec<bool>,
+    info: &'static [RegisterInfo],
 }
 
 impl Registers {
 
     pub fn new(reg_type: RegistersType) -> Registers {
+        let info: &'static [RegisterInfo] = match reg_type {
+            RegistersType::X86 => &X86_REGISTERS,
+            RegistersType::ARM => &ARM_REGISTERS,
+        };
         Self {
-            avail: [true; 4],
-            name: match reg_type {
-                RegistersType::X86 => X86_REGISTERS,
-                RegistersType::ARM => ARM_REGISTERS,
-            },
+            avail: vec![true; info.len()],
+            used: vec![false; info.len()],
+            info,
         }
     }
 
     pub fn init_registers(&mut self) {
-        for i in 0..4 {
+        for i in 0..self.info.len() {
             self.avail[i] = true;
+            self.used[i] = false;
         }
     }
 
     pub fn allocate_register(&mut self) -> Result<usize> {
-        for i in 0..4 {
-            if self.avail[i] {
+        self.allocate(RegisterClass::General)
+    }
+
+    pub fn allocate_float_register(&mut self) -> Result<usize> {
+        self.allocate(RegisterClass::Float)
+    }
+
+    pub fn allocate(&mut self, class: RegisterClass) -> Result<usize> {
+        for i in 0..self.info.len() {
+            if self.avail[i] && self.info[i].class == class {
                 self.avail[i] = false;
+                self.used[i] = true;
                 return Ok(i);
             }
         }
@@ -43,16 +80,126 @@ impl Registers {
         self.avail[reg] = true;
     }
 
+    /**
+    Marks a specific register as allocated, it has to be free
+    */
+    pub fn claim_register(&mut self, reg: usize) {
+        debug_assert!(self.avail[reg], "register {} is already allocated", reg);
+        self.avail[reg] = false;
+        self.used[reg] = true;
+    }
+
+    pub fn is_free(&self, reg: usize) -> bool {
+        self.avail[reg]
+    }
+
+    pub fn free_count(&self, class: RegisterClass) -> usize {
+        (0..self.info.len())
+            .filter(|reg| self.avail[*reg] && self.info[*reg].class == class)
+            .count()
+    }
+
+    pub fn class(&self, reg: usize) -> RegisterClass {
+        self.info[reg].class
+    }
+
+    pub fn is_float(&self, reg: usize) -> bool {
+        self.info[reg].class == RegisterClass::Float
+    }
+
+    /**
+    Caller-saved registers currently holding a value, they do not survive a call
+    */
+    pub fn live_caller_saved(&self) -> Vec<usize> {
+        (0..self.info.len())
+            .filter(|reg| !self.avail[*reg] && !self.info[*reg].callee_saved)
+            .collect()
+    }
+
+    /**
+    Callee-saved registers written since `init_registers`, a function has to restore them
+    */
+    pub fn used_callee_saved(&self) -> Vec<usize> {
+        (0..self.info.len())
+            .filter(|reg| self.used[*reg] && self.info[*reg].callee_saved)
+            .collect()
+    }
+
     pub fn register_name(&self, reg: usize) -> &str {
-        self.name[reg]
+        self.info[reg].names[0]
+    }
+
+    pub fn register_name_sized(&self, reg: usize, size: usize) -> &str {
+        sized_name(&self.info[reg].names, size)
     }
 
 }
 
-static X86_REGISTERS: [&'static str; 4] = [
-    "%r8", "%r9", "%r10", "%r11",
+pub fn sized_name(names: &[&'static str; 4], size: usize) -> &'static str {
+    match size {
+        8 => names[0],
+        4 => names[1],
+        2 => names[2],
+        1 => names[3],
+        other => panic!("Invalid register size: {}", other),
+    }
+}
+
+/**
+ %rax, %rcx and %rdx are left out: they are implicit operands of division, shifts and returns,
+ and so is %xmm0 which returns floating point values
+ */
+static X86_REGISTERS: [RegisterInfo; 26] = [
+    RegisterInfo { names: ["%r8", "%r8d", "%r8w", "%r8b"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["%r9", "%r9d", "%r9w", "%r9b"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["%r10", "%r10d", "%r10w", "%r10b"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["%r11", "%r11d", "%r11w", "%r11b"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["%rsi", "%esi", "%si", "%sil"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["%rdi", "%edi", "%di", "%dil"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["%rbx", "%ebx", "%bx", "%bl"], class: RegisterClass::General, callee_saved: true },
+    RegisterInfo { names: ["%r12", "%r12d", "%r12w", "%r12b"], class: RegisterClass::General, callee_saved: true },
+    RegisterInfo { names: ["%r13", "%r13d", "%r13w", "%r13b"], class: RegisterClass::General, callee_saved: true },
+    RegisterInfo { names: ["%r14", "%r14d", "%r14w", "%r14b"], class: RegisterClass::General, callee_saved: true },
+    RegisterInfo { names: ["%r15", "%r15d", "%r15w", "%r15b"], class: RegisterClass::General, callee_saved: true },
+    RegisterInfo { names: ["%xmm8"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm9"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm10"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm11"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm12"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm13"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm14"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm15"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm1"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm2"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm3"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm4"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm5"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm6"; 4], class: RegisterClass::Float, callee_saved: false },
+    RegisterInfo { names: ["%xmm7"; 4], class: RegisterClass::Float, callee_saved: false },
+];
+
+/**
+ Integer argument registers of the System V AMD64 calling convention, in order
+ */
+pub static X86_ARGUMENT_REGISTERS: [[&str; 4]; 6] = [
+    ["%rdi", "%edi", "%di", "%dil"],
+    ["%rsi", "%esi", "%si", "%sil"],
+    ["%rdx", "%edx", "%dx", "%dl"],
+    ["%rcx", "%ecx", "%cx", "%cl"],
+    ["%r8", "%r8d", "%r8w", "%r8b"],
+    ["%r9", "%r9d", "%r9w", "%r9b"],
+];
+
+/**
+ Floating point argument registers of the System V AMD64 calling convention, in order
+ */
+pub static X86_FLOAT_ARGUMENT_REGISTERS: [&str; 8] = [
+    "%xmm0", "%xmm1", "%xmm2", "%xmm3", "%xmm4", "%xmm5", "%xmm6", "%xmm7",
 ];
 
-static ARM_REGISTERS: [&'static str; 4] = [
-    "r0", "r1", "r2", "r3",
-];
\ No newline at end of file
+static ARM_REGISTERS: [RegisterInfo; 4] = [
+    RegisterInfo { names: ["r0", "r0", "r0", "r0"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["r1", "r1", "r1", "r1"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["r2", "r2", "r2", "r2"], class: RegisterClass::General, callee_saved: false },
+    RegisterInfo { names: ["r3", "r3", "r3", "r3"], class: RegisterClass::General, callee_saved: false },
+];
@@ -1,80 +1,715 @@
-use crate::codegen::core::{CodeGen, RegLoadable};
-use crate::codegen::module::output::FileOutput;
-use crate::codegen::module::registers::{Registers, RegistersType};
+use crate::codegen::core::{CodeGen, CompareOp, RegLoadable};
+use crate::codegen::module::output::{FileOutput, TextBuffer};
+use crate::codegen::module::registers::{
+    sized_name, RegisterClass, Registers, RegistersType, X86_ARGUMENT_REGISTERS, X86_FLOAT_ARGUMENT_REGISTERS
+};
+use crate::common::errors::error::CompilerErrorKind;
 use crate::file_writeln;
+use crate::parser::data_type::DataType;
+
+/**
+ Book keeping of a call whose arguments are being pushed
+ */
+struct PendingCall {
+    /**
+    Caller-saved registers pushed before the call, restored once it returns
+    */
+    saved: Vec<usize>,
+    /**
+    Bytes reserved to keep %rsp 16-byte aligned at the call instruction
+    */
+    padding: usize,
+    /**
+    Arguments passed in general purpose registers, in vector registers and on the stack
+    */
+    register_args: usize,
+    float_register_args: usize,
+    stack_args: usize,
+}
 
 pub struct X86Generator<'a> {
     registers: Registers,
     output: &'a mut FileOutput,
+
+    /**
+    Function body, buffered until the frame size is known
+    */
+    body: TextBuffer,
+    function_name: String,
+    return_label: usize,
+    stack_size: usize,
+    /**
+    Bytes pushed below the fixed frame, used to align %rsp at calls
+    */
+    push_depth: usize,
+    calls: Vec<PendingCall>,
+    /**
+    Frame offsets of spill slots, and the class of the register spilled to each one in use
+    */
+    spill_slots: Vec<(i64, Option<RegisterClass>)>,
+    /**
+    Floating constants by bit pattern and size, emitted to .rodata once all code is generated
+    */
+    float_constants: Vec<(u64, usize)>,
+    /**
+    String literals, emitted to .rodata like the floating constants
+    */
+    string_constants: Vec<Vec<u8>>,
+    label_count: usize,
 }
 
 impl<'a> X86Generator<'a> {
-    pub fn new(output: &mut FileOutput) -> X86Generator {
+    pub fn new(output: &mut FileOutput) -> X86Generator<'_> {
         X86Generator {
             registers: Registers::new(RegistersType::X86),
             output,
+
+            body: TextBuffer::new(),
+            function_name: String::new(),
+            return_label: 0,
+            stack_size: 0,
+            push_depth: 0,
+            calls: Vec::new(),
+            spill_slots: Vec::new(),
+            float_constants: Vec::new(),
+            string_constants: Vec::new(),
+            label_count: 0,
+        }
+    }
+
+    fn allocate_slot(&mut self, size: usize) -> i64 {
+        self.stack_size = (self.stack_size + size).next_multiple_of(size);
+        -(self.stack_size as i64)
+    }
+
+    /**
+    Moves a `data_type` value from `src` into the full register `reg`, sign or zero extending it.
+    Register values are always kept extended this way
+    */
+    fn extend(&mut self, src: &str, reg: usize, data_type: DataType) -> crate::common::Result<()> {
+        if data_type.is_floating() {
+            return self.body.writeln(&format!(
+                "\t{}\t{}, {}",
+                Self::float_move(data_type),
+                src,
+                self.registers.register_name(reg)
+            ));
+        }
+        let instruction = match (data_type.size(), data_type.is_signed()) {
+            (1, true) => "movsbq",
+            (1, false) => "movzbq",
+            (2, true) => "movswq",
+            (2, false) => "movzwq",
+            (4, true) => "movslq",
+            // writing a 32-bit register clears the upper half
+            (4, false) => return self.body.writeln(&format!(
+                "\tmovl\t{}, {}",
+                src,
+                self.registers.register_name_sized(reg, 4)
+            )),
+            _ => "movq",
+        };
+        self.body.writeln(&format!(
+            "\t{}\t{}, {}",
+            instruction,
+            src,
+            self.registers.register_name(reg)
+        ))
+    }
+
+    fn store_instruction(data_type: DataType) -> &'static str {
+        if data_type.is_floating() {
+            return Self::float_move(data_type);
+        }
+        match data_type.size() {
+            1 => "movb",
+            2 => "movw",
+            4 => "movl",
+            _ => "movq",
+        }
+    }
+
+    fn float_move(data_type: DataType) -> &'static str {
+        match data_type {
+            DataType::Float => "movss",
+            _ => "movsd",
+        }
+    }
+
+    /**
+    `op` followed by the ss (float) or sd (double) suffix of scalar SSE instructions
+    */
+    fn float_instruction(op: &str, data_type: DataType) -> String {
+        match data_type {
+            DataType::Float => format!("{}ss", op),
+            _ => format!("{}sd", op),
+        }
+    }
+
+    /**
+    A type whose moves copy a whole register of `class`
+    */
+    fn spill_type(class: RegisterClass) -> DataType {
+        match class {
+            RegisterClass::General => DataType::Long,
+            RegisterClass::Float => DataType::Double,
         }
     }
+
+    fn register_class(data_type: DataType) -> RegisterClass {
+        if data_type.is_floating() {
+            RegisterClass::Float
+        } else {
+            RegisterClass::General
+        }
+    }
+
+    /**
+    Label of a floating constant in .rodata, the same constant is only emitted once
+    */
+    fn float_constant(&mut self, value: f64, data_type: DataType) -> String {
+        let constant = match data_type {
+            DataType::Float => ((value as f32).to_bits() as u64, 4),
+            _ => (value.to_bits(), 8),
+        };
+        let index = match self.float_constants.iter().position(|c| *c == constant) {
+            Some(index) => index,
+            None => {
+                self.float_constants.push(constant);
+                self.float_constants.len() - 1
+            }
+        };
+        format!(".LF{}", index)
+    }
+
+    /**
+    Label of a string literal in .rodata, equal literals share the same bytes
+    */
+    fn string_constant(&mut self, value: &[u8]) -> String {
+        let index = match self.string_constants.iter().position(|s| s == value) {
+            Some(index) => index,
+            None => {
+                self.string_constants.push(value.to_vec());
+                self.string_constants.len() - 1
+            }
+        };
+        format!(".LS{}", index)
+    }
+
+    /**
+    `value` as the operand of a .string directive, anything but printable ASCII is escaped in octal
+    */
+    fn string_directive(value: &[u8]) -> String {
+        let mut text = String::from("\"");
+        for byte in value.iter() {
+            match byte {
+                b'"' | b'\\' => {
+                    text.push('\\');
+                    text.push(*byte as char);
+                },
+                b' '..=b'~' => text.push(*byte as char),
+                _ => text.push_str(&format!("\\{:03o}", byte)),
+            }
+        }
+        text.push('"');
+        text
+    }
+
+    /**
+    Copies `src` into `dst` in the register class of `data_type`, either may be a fixed register
+    */
+    fn copy(&mut self, src: &str, dst: &str, data_type: DataType) -> crate::common::Result<()> {
+        // movsd between registers only writes the low 64 bits, which hold floats as well
+        let instruction = if data_type.is_floating() { "movsd" } else { "movq" };
+        self.body.writeln(&format!("\t{}\t{}, {}", instruction, src, dst))
+    }
+
+    fn float_arithmetic(&mut self, op: &str, reg1: usize, reg2: usize, data_type: DataType) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\t{}\t{}, {}",
+            Self::float_instruction(op, data_type),
+            self.registers.register_name(reg2),
+            self.registers.register_name(reg1)
+        ))?;
+        self.registers.free_register(reg2);
+
+        Ok(reg1)
+    }
+
+    /**
+    Converts the integer in `reg` to the floating type `to` into a new register
+    */
+    fn int_to_float(&mut self, reg: usize, from: DataType, to: DataType) -> crate::common::Result<usize> {
+        let result = self.registers.allocate_float_register()?;
+        let (src, dst) = (
+            self.registers.register_name(reg).to_string(),
+            self.registers.register_name(result).to_string(),
+        );
+        let convert = Self::float_instruction("cvtsi2", to) + "q";
+
+        if from.size() == 8 && !from.is_signed() {
+            // halve values with the top bit set, keeping the lowest bit for the rounding,
+            // then double the result again
+            let (big_label, end_label) = (self.cg_new_label(), self.cg_new_label());
+            file_writeln!(self, body, writeln,
+                &format!("\ttestq\t{}, {}", src, src),
+                &format!("\tjs\t{}", Self::label_name(big_label)),
+                &format!("\t{}\t{}, {}", convert, src, dst),
+                &format!("\tjmp\t{}", Self::label_name(end_label)),
+                &format!("{}:", Self::label_name(big_label)),
+                &format!("\tmovq\t{}, %rax", src),
+                "\tshrq\t%rax",
+                &format!("\tmovq\t{}, %rcx", src),
+                "\tandl\t$1, %ecx",
+                "\torq\t%rcx, %rax",
+                &format!("\t{}\t%rax, {}", convert, dst),
+                &format!("\t{}\t{}, {}", Self::float_instruction("add", to), dst, dst),
+                &format!("{}:", Self::label_name(end_label)),
+            );
+        } else {
+            // every other integer type is held extended to a valid signed 64-bit value
+            self.body.writeln(&format!("\t{}\t{}, {}", convert, src, dst))?;
+        }
+        self.registers.free_register(reg);
+
+        Ok(result)
+    }
+
+    /**
+    Converts the floating value in `reg` to the integer type `to` into a new register,
+    truncating toward zero
+    */
+    fn float_to_int(&mut self, reg: usize, from: DataType, to: DataType) -> crate::common::Result<usize> {
+        let result = self.registers.allocate_register()?;
+        let (src, dst) = (
+            self.registers.register_name(reg).to_string(),
+            self.registers.register_name(result).to_string(),
+        );
+        let convert = format!("{}2siq", Self::float_instruction("cvtt", from));
+
+        if to.size() == 8 && !to.is_signed() {
+            // values from 2^63 on do not fit the signed conversion, they are converted
+            // after subtracting 2^63 which is then added back by flipping the top bit
+            let limit = self.float_constant(9223372036854775808.0, from);
+            let (big_label, end_label) = (self.cg_new_label(), self.cg_new_label());
+            file_writeln!(self, body, writeln,
+                &format!("\t{}\t{}(%rip), %xmm0", Self::float_move(from), limit),
+                &format!("\t{}\t%xmm0, {}", Self::float_instruction("ucomi", from), src),
+                &format!("\tjae\t{}", Self::label_name(big_label)),
+                &format!("\t{}\t{}, {}", convert, src, dst),
+                &format!("\tjmp\t{}", Self::label_name(end_label)),
+                &format!("{}:", Self::label_name(big_label)),
+                &format!("\t{}\t%xmm0, {}", Self::float_instruction("sub", from), src),
+                &format!("\t{}\t{}, {}", convert, src, dst),
+                &format!("\tbtcq\t$63, {}", dst),
+                &format!("{}:", Self::label_name(end_label)),
+            );
+        } else {
+            self.body.writeln(&format!("\t{}\t{}, {}", convert, src, dst))?;
+        }
+        self.registers.free_register(reg);
+
+        self.cg_wrap(result, to)
+    }
+
+    /**
+    Divides %rdx:%rax by `reg2` after loading `reg1` into %rax, the quotient is left in %rax
+    and the remainder in %rdx
+    */
+    fn divide(&mut self, reg1: usize, reg2: usize, signed: bool) -> crate::common::Result<()> {
+        self.body.writeln(&format!(
+            "\tmovq\t{}, %rax",
+            self.registers.register_name(reg1)
+        ))?;
+        if signed {
+            self.body.writeln("\tcqo")?;
+        } else {
+            self.body.writeln("\txorl\t%edx, %edx")?;
+        }
+        self.body.writeln(&format!(
+            "\t{}\t{}",
+            if signed { "idivq" } else { "divq" },
+            self.registers.register_name(reg2)
+        ))
+    }
+
+    fn shift(&mut self, reg1: usize, reg2: usize, instruction: &str) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\tmovq\t{}, %rcx",
+            self.registers.register_name(reg2)
+        ))?;
+        self.body.writeln(&format!(
+            "\t{}\t%cl, {}",
+            instruction,
+            self.registers.register_name(reg1)
+        ))?;
+        self.registers.free_register(reg2);
+
+        Ok(reg1)
+    }
+
+    fn label_name(label: usize) -> String {
+        format!(".L{}", label)
+    }
+
+    fn set_flag(&mut self, reg: usize, set_instruction: &str) -> crate::common::Result<()> {
+        self.body.writeln(&format!(
+            "\t{}\t{}",
+            set_instruction,
+            self.registers.register_name_sized(reg, 1)
+        ))?;
+        self.body.writeln(&format!(
+            "\tmovzbq\t{}, {}",
+            self.registers.register_name_sized(reg, 1),
+            self.registers.register_name(reg)
+        ))
+    }
+
+    /**
+    Stack frame size rounded up to keep %rsp 16-byte aligned
+    */
+    fn frame_size(&self) -> usize {
+        self.stack_size.next_multiple_of(16)
+    }
 }
 
 impl CodeGen for X86Generator<'_> {
     fn cg_pre_amble(&mut self) -> crate::common::Result<()> {
+        // formats of the print statement
+        file_writeln!(self, output, writeln,
+            "\t.section\t.rodata",
+            ".LCSTRING:",
+            "\t.string\t\"%s\\n\"",
+            ".LCDOUBLE:",
+            "\t.string\t\"%f\\n\"",
+            ".LCINT:",
+            "\t.string\t\"%d\\n\"",
+            ".LCUINT:",
+            "\t.string\t\"%u\\n\"",
+            ".LCLONG:",
+            "\t.string\t\"%ld\\n\"",
+            ".LCULONG:",
+            "\t.string\t\"%lu\\n\"",
+            "\t.text",
+        );
+
+        Ok(())
+    }
+
+    fn cg_post_amble(&mut self) -> crate::common::Result<()> {
+        if !self.float_constants.is_empty() || !self.string_constants.is_empty() {
+            file_writeln!(self, output, writeln,
+                "",
+                "\t.section\t.rodata",
+            );
+        }
+        for (index, (bits, size)) in self.float_constants.iter().enumerate() {
+            let (directive, bits) = match size {
+                4 => (".long", *bits as u32 as u64),
+                _ => (".quad", *bits),
+            };
+            file_writeln!(self, output, writeln,
+                &format!("\t.align\t{}", size),
+                &format!(".LF{}:", index),
+                &format!("\t{}\t{}", directive, bits),
+            );
+        }
+        for (index, value) in self.string_constants.iter().enumerate() {
+            file_writeln!(self, output, writeln,
+                &format!(".LS{}:", index),
+                &format!("\t.string\t{}", Self::string_directive(value)),
+            );
+        }
+
+        // the generated code never needs an executable stack
+        file_writeln!(self, output, writeln,
+            "",
+            "\t.section\t.note.GNU-stack,\"\",@progbits",
+        );
+
+        Ok(())
+    }
+
+    fn cg_function_preamble(&mut self, name: &str) -> crate::common::Result<()> {
         self.registers.init_registers();
 
+        self.function_name = name.to_string();
+        self.return_label = self.cg_new_label();
+        self.stack_size = 0;
+        self.push_depth = 0;
+        self.calls.clear();
+        self.spill_slots.clear();
+
+        Ok(())
+    }
+
+    fn cg_declare_parameters(&mut self, param_types: &[DataType]) -> crate::common::Result<Vec<i64>> {
+        let (mut register_params, mut float_register_params, mut stack_params) = (0, 0, 0);
+        let mut offsets = Vec::with_capacity(param_types.len());
+
+        for data_type in param_types.iter() {
+            let register = if data_type.is_floating() && float_register_params < X86_FLOAT_ARGUMENT_REGISTERS.len() {
+                float_register_params += 1;
+                X86_FLOAT_ARGUMENT_REGISTERS[float_register_params - 1]
+            } else if !data_type.is_floating() && register_params < X86_ARGUMENT_REGISTERS.len() {
+                register_params += 1;
+                sized_name(&X86_ARGUMENT_REGISTERS[register_params - 1], data_type.size())
+            } else {
+                // passed on the stack, right above the saved %rbp and the return address
+                offsets.push(16 + 8 * stack_params);
+                stack_params += 1;
+                continue;
+            };
+
+            let offset = self.allocate_slot(data_type.size());
+            self.body.writeln(&format!(
+                "\t{}\t{}, {}(%rbp)",
+                Self::store_instruction(*data_type),
+                register,
+                offset
+            ))?;
+            offsets.push(offset);
+        }
+
+        Ok(offsets)
+    }
+
+    fn cg_function_postamble(&mut self) -> crate::common::Result<()> {
+        if self.function_name == "main" {
+            // reaching the end of main returns 0
+            self.body.writeln("\tmovl\t$0, %eax")?;
+        }
+
+        let callee_saved: Vec<(usize, i64)> = self.registers.used_callee_saved()
+            .into_iter()
+            .map(|reg| (reg, self.allocate_slot(8)))
+            .collect();
+        let frame_size = self.frame_size();
+
         file_writeln!(self, output, writeln,
-            ".text",
-            ".LC0:",
-            "\t.string\t\"%d\\n\"",
-            "printint:",
-            "\tpushq\t%rbp",
-            "\tmovq\t%rsp, %rbp",
-            "\tsubq\t$16, %rsp",
-            "\tmovl\t%edi, -4(%rbp)",
-            "\tmovl\t-4(%rbp), %eax",
-            "\tmovl\t%eax, %esi",
-            "\tleaq\t.LC0(%rip), %rdi",
-            "\tmovl\t$0, %eax",
-            "\tcall\tprintf@PLT",
-            "\tnop",
-            "\tleave",
-            "\tret",
             "",
-            ".globl\tmain",
-            ".type\tmain, @function",
-            "main:",
+            &format!(".globl\t{}", self.function_name),
+            &format!(".type\t{}, @function", self.function_name),
+            &format!("{}:", self.function_name),
             "\tpushq\t%rbp",
             "\tmovq\t%rsp, %rbp",
         );
+        if frame_size > 0 {
+            self.output.writeln(&format!("\tsubq\t${}, %rsp", frame_size))?;
+        }
+        for (reg, offset) in callee_saved.iter() {
+            self.output.writeln(&format!(
+                "\tmovq\t{}, {}(%rbp)",
+                self.registers.register_name(*reg),
+                offset
+            ))?;
+        }
 
-        Ok(())
-    }
+        self.body.flush_into(self.output)?;
 
-    fn cg_post_amble(&mut self) -> crate::common::Result<()> {
-        /**
-            return 0;
-            iret -> exit(main());
-        */
+        self.output.writeln(&format!("{}:", Self::label_name(self.return_label)))?;
+        for (reg, offset) in callee_saved.iter() {
+            self.output.writeln(&format!(
+                "\tmovq\t{}(%rbp), {}",
+                offset,
+                self.registers.register_name(*reg)
+            ))?;
+        }
         file_writeln!(self, output, writeln,
-            "\tmovl\t$0, %eax",
-            "\tpopq\t%rbp",
+            "\tleave",
             "\tret",
         );
 
         Ok(())
     }
 
+    fn cg_return(&mut self, reg: Option<usize>) -> crate::common::Result<()> {
+        if let Some(reg) = reg {
+            let (result, data_type) = match self.registers.class(reg) {
+                RegisterClass::Float => ("%xmm0", DataType::Double),
+                RegisterClass::General => ("%rax", DataType::Long),
+            };
+            let src = self.registers.register_name(reg).to_string();
+            self.copy(&src, result, data_type)?;
+            self.registers.free_register(reg);
+        }
+        self.cg_jump(self.return_label)
+    }
+
+    fn cg_call_prepare(&mut self, arg_types: &[DataType]) -> crate::common::Result<Vec<usize>> {
+        let saved = self.registers.live_caller_saved();
+        for reg in saved.iter() {
+            self.cg_push(*reg)?;
+        }
+
+        // the first six integer and the first eight floating arguments are passed in registers,
+        // the others on the stack
+        let (mut register_args, mut float_register_args, mut stack_args) = (vec!(), vec!(), vec!());
+        for (index, data_type) in arg_types.iter().enumerate() {
+            if data_type.is_floating() && float_register_args.len() < X86_FLOAT_ARGUMENT_REGISTERS.len() {
+                float_register_args.push(index);
+            } else if !data_type.is_floating() && register_args.len() < X86_ARGUMENT_REGISTERS.len() {
+                register_args.push(index);
+            } else {
+                stack_args.push(index);
+            }
+        }
+
+        let padding = (self.push_depth + 8 * stack_args.len()) % 16;
+        if padding > 0 {
+            self.body.writeln(&format!("\tsubq\t${}, %rsp", padding))?;
+            self.push_depth += padding;
+        }
+
+        self.calls.push(PendingCall {
+            saved,
+            padding,
+            register_args: register_args.len(),
+            float_register_args: float_register_args.len(),
+            stack_args: stack_args.len(),
+        });
+
+        // pushed last to first: the stack arguments end up in order above the return address,
+        // and the register arguments are popped from the first one on
+        Ok(stack_args.into_iter().rev()
+            .chain(float_register_args.into_iter().rev())
+            .chain(register_args.into_iter().rev())
+            .collect())
+    }
+
+    fn cg_call(&mut self, name: &str, return_type: DataType) -> crate::common::Result<usize> {
+        let Some(call) = self.calls.pop() else {
+            return Err(CompilerErrorKind::InternalError(
+                format!("Call to '{}' was not prepared", name)
+            ));
+        };
+
+        for arg_register in X86_ARGUMENT_REGISTERS.iter().take(call.register_args) {
+            self.body.writeln(&format!("\tpopq\t{}", arg_register[0]))?;
+            self.push_depth -= 8;
+        }
+        for arg_register in X86_FLOAT_ARGUMENT_REGISTERS.iter().take(call.float_register_args) {
+            file_writeln!(self, body, writeln,
+                &format!("\tmovsd\t(%rsp), {}", arg_register),
+                "\taddq\t$8, %rsp",
+            );
+            self.push_depth -= 8;
+        }
+
+        // %al holds the number of vector registers used by a variadic callee
+        self.body.writeln(&format!("\tmovl\t${}, %eax", call.float_register_args))?;
+        self.body.writeln(&format!("\tcall\t{}", name))?;
+
+        let cleanup = 8 * call.stack_args + call.padding;
+        if cleanup > 0 {
+            self.body.writeln(&format!("\taddq\t${}, %rsp", cleanup))?;
+            self.push_depth -= cleanup;
+        }
+
+        let reg = self.registers.allocate(Self::register_class(return_type))?;
+        if return_type.is_floating() {
+            self.extend("%xmm0", reg, DataType::Double)?;
+        } else {
+            // only the low bytes of %rax are defined for narrow return types
+            let size = match return_type {
+                DataType::Void => 8,
+                other => other.size(),
+            };
+            self.extend(sized_name(&["%rax", "%eax", "%ax", "%al"], size), reg, return_type)?;
+        }
+
+        for saved in call.saved.iter().rev() {
+            self.cg_pop(*saved)?;
+        }
+        Ok(reg)
+    }
+
     fn cg_push(&mut self, reg: usize) -> crate::common::Result<()> {
-        todo!()
+        if self.registers.is_float(reg) {
+            file_writeln!(self, body, writeln,
+                "\tsubq\t$8, %rsp",
+                &format!("\tmovsd\t{}, (%rsp)", self.registers.register_name(reg)),
+            );
+        } else {
+            self.body.writeln(&format!(
+                "\tpushq\t{}",
+                self.registers.register_name(reg)
+            ))?;
+        }
+        self.push_depth += 8;
+        Ok(())
     }
 
     fn cg_pop(&mut self, reg: usize) -> crate::common::Result<()> {
-        todo!()
+        if self.registers.is_float(reg) {
+            file_writeln!(self, body, writeln,
+                &format!("\tmovsd\t(%rsp), {}", self.registers.register_name(reg)),
+                "\taddq\t$8, %rsp",
+            );
+        } else {
+            self.body.writeln(&format!(
+                "\tpopq\t{}",
+                self.registers.register_name(reg)
+            ))?;
+        }
+        self.push_depth -= 8;
+        Ok(())
+    }
+
+    fn cg_free_register(&mut self, reg: usize) {
+        self.registers.free_register(reg);
+    }
+
+    fn cg_registers_low(&self) -> bool {
+        // the most any expression node holds at once, e.g. `x++` needs the old value,
+        // the current value and the increment
+        self.registers.free_count(RegisterClass::General) < 3
+            || self.registers.free_count(RegisterClass::Float) < 3
+    }
+
+    fn cg_spill(&mut self, reg: usize) -> crate::common::Result<usize> {
+        let slot = match self.spill_slots.iter().position(|(_, class)| class.is_none()) {
+            Some(slot) => slot,
+            None => {
+                let offset = self.allocate_slot(8);
+                self.spill_slots.push((offset, None));
+                self.spill_slots.len() - 1
+            }
+        };
+        let class = self.registers.class(reg);
+        self.spill_slots[slot].1 = Some(class);
+
+        let (src, dst) = (
+            self.registers.register_name(reg).to_string(),
+            format!("{}(%rbp)", self.spill_slots[slot].0),
+        );
+        self.copy(&src, &dst, Self::spill_type(class))?;
+        self.registers.free_register(reg);
+
+        Ok(slot)
+    }
+
+    fn cg_reload(&mut self, slot: usize) -> crate::common::Result<usize> {
+        let Some(class) = self.spill_slots[slot].1.take() else {
+            return Err(CompilerErrorKind::InternalError(
+                format!("Spill slot {} holds no value", slot)
+            ));
+        };
+        let reg = self.registers.allocate(class)?;
+
+        let (src, dst) = (
+            format!("{}(%rbp)", self.spill_slots[slot].0),
+            self.registers.register_name(reg).to_string(),
+        );
+        self.copy(&src, &dst, Self::spill_type(class))?;
+
+        Ok(reg)
     }
 
     fn cg_load<T: RegLoadable>(&mut self, value: T) -> crate::common::Result<usize> {
         let reg = self.registers.allocate_register()?;
 
-        self.output.writeln(&format!(
+        self.body.writeln(&format!(
             "\tmovq\t{}, {}",
             value.to_gnu_x86(),
             self.registers.register_name(reg)
@@ -83,8 +718,50 @@ impl CodeGen for X86Generator<'_> {
         Ok(reg)
     }
 
+    fn cg_load_float(&mut self, value: f64, data_type: DataType) -> crate::common::Result<usize> {
+        let reg = self.registers.allocate_float_register()?;
+        let constant = self.float_constant(value, data_type);
+        self.extend(&format!("{}(%rip)", constant), reg, data_type)?;
+
+        Ok(reg)
+    }
+
+    fn cg_load_string(&mut self, value: &[u8]) -> crate::common::Result<usize> {
+        let reg = self.registers.allocate_register()?;
+        let constant = self.string_constant(value);
+        self.body.writeln(&format!(
+            "\tleaq\t{}(%rip), {}",
+            constant,
+            self.registers.register_name(reg)
+        ))?;
+
+        Ok(reg)
+    }
+
+    fn cg_declare_local(&mut self, data_type: DataType) -> crate::common::Result<i64> {
+        Ok(self.allocate_slot(data_type.size()))
+    }
+
+    fn cg_load_local(&mut self, offset: i64, data_type: DataType) -> crate::common::Result<usize> {
+        let reg = self.registers.allocate(Self::register_class(data_type))?;
+        self.extend(&format!("{}(%rbp)", offset), reg, data_type)?;
+
+        Ok(reg)
+    }
+
+    fn cg_store_local(&mut self, reg: usize, offset: i64, data_type: DataType) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\t{}\t{}, {}(%rbp)",
+            Self::store_instruction(data_type),
+            self.registers.register_name_sized(reg, data_type.size()),
+            offset
+        ))?;
+
+        Ok(reg)
+    }
+
     fn cg_add(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
-        self.output.writeln(&format!(
+        self.body.writeln(&format!(
             "\taddq\t{}, {}",
             self.registers.register_name(reg1),
             self.registers.register_name(reg2)
@@ -95,7 +772,7 @@ impl CodeGen for X86Generator<'_> {
     }
 
     fn cg_sub(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
-        self.output.writeln(&format!(
+        self.body.writeln(&format!(
             "\tsubq\t{}, {}",
             self.registers.register_name(reg2),
             self.registers.register_name(reg1)
@@ -106,7 +783,7 @@ impl CodeGen for X86Generator<'_> {
     }
 
     fn cg_mul(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
-        self.output.writeln(&format!(
+        self.body.writeln(&format!(
             "\timulq\t{}, {}",
             self.registers.register_name(reg1),
             self.registers.register_name(reg2)
@@ -117,33 +794,560 @@ impl CodeGen for X86Generator<'_> {
     }
 
     fn cg_div(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
-        self.output.writeln(&format!(
-            "\tmovq\t{}, %rax",
+        self.divide(reg1, reg2, true)?;
+        self.body.writeln(&format!(
+            "\tmovq\t%rax, {}",
             self.registers.register_name(reg1)
         ))?;
-        file_writeln!(self, output, writeln,
-            "\tcqo");
-        self.output.writeln(&format!(
-            "\tidivq\t{}",
+        self.registers.free_register(reg2);
+
+        Ok(reg1)
+    }
+
+    fn cg_udiv(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.divide(reg1, reg2, false)?;
+        self.body.writeln(&format!(
+            "\tmovq\t%rax, {}",
+            self.registers.register_name(reg1)
+        ))?;
+        self.registers.free_register(reg2);
+
+        Ok(reg1)
+    }
+
+    fn cg_mod(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.divide(reg1, reg2, true)?;
+        self.body.writeln(&format!(
+            "\tmovq\t%rdx, {}",
+            self.registers.register_name(reg1)
+        ))?;
+        self.registers.free_register(reg2);
+
+        Ok(reg1)
+    }
+
+    fn cg_umod(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.divide(reg1, reg2, false)?;
+        self.body.writeln(&format!(
+            "\tmovq\t%rdx, {}",
+            self.registers.register_name(reg1)
+        ))?;
+        self.registers.free_register(reg2);
+
+        Ok(reg1)
+    }
+
+    fn cg_shl(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.shift(reg1, reg2, "salq")
+    }
+
+    fn cg_shr(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.shift(reg1, reg2, "sarq")
+    }
+
+    fn cg_ushr(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.shift(reg1, reg2, "shrq")
+    }
+
+    fn cg_and(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\tandq\t{}, {}",
+            self.registers.register_name(reg1),
             self.registers.register_name(reg2)
         ))?;
-        self.output.writeln(&format!(
-            "\tmovq\t%rax, {}",
+        self.registers.free_register(reg1);
+
+        Ok(reg2)
+    }
+
+    fn cg_or(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\torq\t{}, {}",
+            self.registers.register_name(reg1),
+            self.registers.register_name(reg2)
+        ))?;
+        self.registers.free_register(reg1);
+
+        Ok(reg2)
+    }
+
+    fn cg_xor(&mut self, reg1: usize, reg2: usize) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\txorq\t{}, {}",
+            self.registers.register_name(reg1),
+            self.registers.register_name(reg2)
+        ))?;
+        self.registers.free_register(reg1);
+
+        Ok(reg2)
+    }
+
+    fn cg_compare(&mut self, reg1: usize, reg2: usize, op: CompareOp) -> crate::common::Result<usize> {
+        let set_instruction = match op {
+            CompareOp::Equal => "sete",
+            CompareOp::NotEqual => "setne",
+            CompareOp::Less => "setl",
+            CompareOp::LessEqual => "setle",
+            CompareOp::Greater => "setg",
+            CompareOp::GreaterEqual => "setge",
+            CompareOp::UnsignedLess => "setb",
+            CompareOp::UnsignedLessEqual => "setbe",
+            CompareOp::UnsignedGreater => "seta",
+            CompareOp::UnsignedGreaterEqual => "setae",
+        };
+        self.body.writeln(&format!(
+            "\tcmpq\t{}, {}",
+            self.registers.register_name(reg2),
             self.registers.register_name(reg1)
         ))?;
+        self.set_flag(reg1, set_instruction)?;
         self.registers.free_register(reg2);
 
         Ok(reg1)
     }
 
-    fn cg_printreg(&mut self, reg: usize) -> crate::common::Result<()> {
-        self.output.writeln(&format!(
-            "\tmovq\t{}, %rdi",
+    fn cg_fadd(&mut self, reg1: usize, reg2: usize, data_type: DataType) -> crate::common::Result<usize> {
+        self.float_arithmetic("add", reg1, reg2, data_type)
+    }
+
+    fn cg_fsub(&mut self, reg1: usize, reg2: usize, data_type: DataType) -> crate::common::Result<usize> {
+        self.float_arithmetic("sub", reg1, reg2, data_type)
+    }
+
+    fn cg_fmul(&mut self, reg1: usize, reg2: usize, data_type: DataType) -> crate::common::Result<usize> {
+        self.float_arithmetic("mul", reg1, reg2, data_type)
+    }
+
+    fn cg_fdiv(&mut self, reg1: usize, reg2: usize, data_type: DataType) -> crate::common::Result<usize> {
+        self.float_arithmetic("div", reg1, reg2, data_type)
+    }
+
+    fn cg_fcompare(&mut self, reg1: usize, reg2: usize, op: CompareOp, data_type: DataType) -> crate::common::Result<usize> {
+        // ucomis sets CF and ZF like an unsigned compare, plus PF when unordered, i.e. NaN.
+        // a and ae are false for unordered operands, so less than is tested swapped
+        let (left, right, set_instruction) = match op {
+            CompareOp::Greater => (reg1, reg2, "seta"),
+            CompareOp::GreaterEqual => (reg1, reg2, "setae"),
+            CompareOp::Less => (reg2, reg1, "seta"),
+            CompareOp::LessEqual => (reg2, reg1, "setae"),
+            CompareOp::Equal => (reg1, reg2, "sete"),
+            CompareOp::NotEqual => (reg1, reg2, "setne"),
+            other => unimplemented!("{:?} on floating operands", other),
+        };
+        self.body.writeln(&format!(
+            "\t{}\t{}, {}",
+            Self::float_instruction("ucomi", data_type),
+            self.registers.register_name(right),
+            self.registers.register_name(left)
+        ))?;
+        self.registers.free_register(reg1);
+        self.registers.free_register(reg2);
+
+        let reg = self.registers.allocate_register()?;
+        let result = self.registers.register_name_sized(reg, 1).to_string();
+        self.body.writeln(&format!("\t{}\t{}", set_instruction, result))?;
+        match op {
+            CompareOp::Equal => {
+                file_writeln!(self, body, writeln,
+                    "\tsetnp\t%cl",
+                    &format!("\tandb\t%cl, {}", result),
+                );
+            },
+            CompareOp::NotEqual => {
+                file_writeln!(self, body, writeln,
+                    "\tsetp\t%cl",
+                    &format!("\torb\t%cl, {}", result),
+                );
+            },
+            _ => {},
+        }
+        self.body.writeln(&format!(
+            "\tmovzbq\t{}, {}",
+            result,
+            self.registers.register_name(reg)
+        ))?;
+
+        Ok(reg)
+    }
+
+    fn cg_fneg(&mut self, reg: usize, data_type: DataType) -> crate::common::Result<usize> {
+        // flips the sign bit, which also negates zeros and NaNs
+        let name = self.registers.register_name(reg).to_string();
+        match data_type {
+            DataType::Float => {
+                file_writeln!(self, body, writeln,
+                    &format!("\tmovd\t{}, %eax", name),
+                    "\txorl\t$0x80000000, %eax",
+                    &format!("\tmovd\t%eax, {}", name),
+                );
+            },
+            _ => {
+                file_writeln!(self, body, writeln,
+                    &format!("\tmovq\t{}, %rax", name),
+                    "\tbtcq\t$63, %rax",
+                    &format!("\tmovq\t%rax, {}", name),
+                );
+            },
+        }
+
+        Ok(reg)
+    }
+
+    fn cg_cast(&mut self, reg: usize, from: DataType, to: DataType) -> crate::common::Result<usize> {
+        match (from.is_floating(), to.is_floating()) {
+            (false, true) => return self.int_to_float(reg, from, to),
+            (true, false) => return self.float_to_int(reg, from, to),
+            (true, true) => {
+                if from != to {
+                    let name = self.registers.register_name(reg);
+                    self.body.writeln(&format!(
+                        "\t{}\t{}, {}",
+                        if to == DataType::Double { "cvtss2sd" } else { "cvtsd2ss" },
+                        name,
+                        name
+                    ))?;
+                }
+                return Ok(reg);
+            },
+            (false, false) => {},
+        }
+
+        // widening keeps the extended value as it is, only narrowing or changing the
+        // signedness of a narrow type has to extend again
+        if to.size() < from.size() || to.is_signed() != from.is_signed() {
+            return self.cg_wrap(reg, to);
+        }
+
+        Ok(reg)
+    }
+
+    fn cg_wrap(&mut self, reg: usize, data_type: DataType) -> crate::common::Result<usize> {
+        if data_type.is_integer() && data_type.size() < 8 {
+            let src = self.registers.register_name_sized(reg, data_type.size()).to_string();
+            self.extend(&src, reg, data_type)?;
+        }
+
+        Ok(reg)
+    }
+
+    fn cg_neg(&mut self, reg: usize) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\tnegq\t{}",
+            self.registers.register_name(reg)
+        ))?;
+
+        Ok(reg)
+    }
+
+    fn cg_bitnot(&mut self, reg: usize) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\tnotq\t{}",
+            self.registers.register_name(reg)
+        ))?;
+
+        Ok(reg)
+    }
+
+    fn cg_to_bool(&mut self, reg: usize) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\tcmpq\t$0, {}",
             self.registers.register_name(reg)
         ))?;
-        file_writeln!(self, output, writeln, "\tcall\tprintint");
+        self.set_flag(reg, "setne")?;
+
+        Ok(reg)
+    }
+
+    fn cg_not(&mut self, reg: usize) -> crate::common::Result<usize> {
+        self.body.writeln(&format!(
+            "\tcmpq\t$0, {}",
+            self.registers.register_name(reg)
+        ))?;
+        self.set_flag(reg, "sete")?;
+
+        Ok(reg)
+    }
+
+    fn cg_move(&mut self, src: usize, dst: usize) -> crate::common::Result<usize> {
+        if src != dst {
+            let (src_name, dst_name) = (
+                self.registers.register_name(src).to_string(),
+                self.registers.register_name(dst).to_string(),
+            );
+            self.copy(&src_name, &dst_name, Self::spill_type(self.registers.class(src)))?;
+            self.registers.free_register(src);
+            if self.registers.is_free(dst) {
+                self.registers.claim_register(dst);
+            }
+        }
+
+        Ok(dst)
+    }
+
+    fn cg_new_label(&mut self) -> usize {
+        self.label_count += 1;
+        self.label_count
+    }
+
+    fn cg_label(&mut self, label: usize) -> crate::common::Result<()> {
+        self.body.writeln(&format!("{}:", Self::label_name(label)))
+    }
+
+    fn cg_jump(&mut self, label: usize) -> crate::common::Result<()> {
+        self.body.writeln(&format!("\tjmp\t{}", Self::label_name(label)))
+    }
+
+    fn cg_jump_if_zero(&mut self, reg: usize, label: usize) -> crate::common::Result<()> {
+        self.body.writeln(&format!(
+            "\tcmpq\t$0, {}",
+            self.registers.register_name(reg)
+        ))?;
+        self.body.writeln(&format!("\tje\t{}", Self::label_name(label)))
+    }
+
+    fn cg_jump_if_not_zero(&mut self, reg: usize, label: usize) -> crate::common::Result<()> {
+        self.body.writeln(&format!(
+            "\tcmpq\t$0, {}",
+            self.registers.register_name(reg)
+        ))?;
+        self.body.writeln(&format!("\tjne\t{}", Self::label_name(label)))
+    }
+
+    fn cg_printreg(&mut self, reg: usize, data_type: DataType) -> crate::common::Result<()> {
+        let src = self.registers.register_name(reg).to_string();
+        if data_type.is_floating() {
+            self.copy(&src, "%xmm0", data_type)?;
+            self.body.writeln("\tleaq\t.LCDOUBLE(%rip), %rdi")?;
+            file_writeln!(self, body, writeln,
+                "\tmovl\t$1, %eax",
+                "\tcall\tprintf@PLT",
+            );
+
+            self.registers.free_register(reg);
+            return Ok(());
+        }
+
+        let format = match (data_type.size(), data_type.is_signed()) {
+            _ if data_type == DataType::CharPointer => ".LCSTRING",
+            (8, true) => ".LCLONG",
+            (8, false) => ".LCULONG",
+            (_, true) => ".LCINT",
+            (_, false) => ".LCUINT",
+        };
+        self.copy(&src, "%rsi", data_type)?;
+        self.body.writeln(&format!("\tleaq\t{}(%rip), %rdi", format))?;
+        file_writeln!(self, body, writeln,
+            "\tmovl\t$0, %eax",
+            "\tcall\tprintf@PLT",
+        );
 
         self.registers.free_register(reg);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::process::Command;
+    use super::*;
+    use crate::driver::driver::STACK_SIZE;
+    use crate::driver::toolchain::{TempDir, Toolchain};
+    use crate::interpreter::ast_interpreter::ASTInterpreter;
+    use crate::parser::parser::Parser;
+    use crate::scanner::scanner::Scanner;
+    use crate::semantic::type_checker::TypeChecker;
+    use crate::source_code::SourceCode;
+
+    /**
+    Writes the assembly of a program to `path`, and returns it. Compiled on a thread with the stack
+    the compiler runs with, the test threads have less
+    */
+    fn compile(text: &str, path: &Path) -> String {
+        std::thread::scope(|scope| {
+            std::thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, || {
+                    let source_code = SourceCode::new(text.to_string());
+                    let mut scanner = Scanner::new(&source_code);
+                    let mut parser = Parser::new(&mut scanner);
+                    let mut ast = parser.parse().unwrap();
+                    let symbols = parser.take_symbols();
+                    TypeChecker::new(&symbols).check(&mut ast).unwrap();
+
+                    let mut output = FileOutput::new(path).unwrap();
+                    ASTInterpreter::new(Box::new(X86Generator::new(&mut output)), symbols).interpret(&*ast).unwrap();
+                    output.flush().unwrap();
+                })
+                .unwrap()
+                .join()
+                .unwrap();
+        });
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    /**
+    Builds a program with the system tools and runs it, returns its assembly and what it printed
+    */
+    fn run(text: &str) -> (String, String) {
+        let dir = TempDir::new().unwrap();
+        let (source, object, executable) = (dir.path().join("a.s"), dir.path().join("a.o"), dir.path().join("a.out"));
+        let assembly = compile(text, &source);
+        let toolchain = Toolchain::from_env();
+        toolchain.assemble(&source, &object).unwrap();
+        toolchain.link(&[object], &[], &executable).unwrap();
+
+        let output = Command::new(&executable).output().unwrap();
+        assert!(output.status.success(), "{}\n{:?}", assembly, output.status);
+        (assembly, String::from_utf8(output.stdout).unwrap())
+    }
+
+    /**
+    The lines of the function `name` in the assembly
+    */
+    fn function<'a>(assembly: &'a str, name: &str) -> Vec<&'a str> {
+        assembly.lines()
+            .skip_while(|line| *line != format!("{}:", name))
+            .take_while(|line| !line.starts_with(".globl"))
+            .collect()
+    }
+
+    #[test]
+    fn test_call_arguments() {
+        // 8 integer and 10 floating arguments: the last two of each kind go on the stack, in
+        // the order of the parameters
+        let text = "
+            double mixed(int a, double b, int c, double d, int e, double f, int g, double h, int i,
+                         double j, int k, double l, int m, double n, int o, double p, double q, double r) {
+                print m;
+                print q;
+                return a + 2*b + 3*c + 4*d + 5*e + 6*f + 7*g + 8*h + 9*i + 10*j + 11*k + 12*l
+                    + 13*m + 14*n + 15*o + 16*p + 17*q + 18*r;
+            }
+            long seven(long a, long b, long c, long d, long e, long f, long g) {
+                double half = g / 2.0;
+                print half;
+                return a + 2*b + 3*c + 4*d + 5*e + 6*f + 7*g;
+            }
+            int main() {
+                print mixed(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18);
+                print seven(1, 2, 3, 4, 5, 6, 7);
+                print 1 + seven(1, 2, 3, 4, 5, 6, 7);
+                print 0.5 + seven(1, 2, 3, 4, 5, 6, 7);
+            }
+        ";
+        let (assembly, output) = run(text);
+        assert_eq!(output, "13\n17.000000\n2109.000000\n3.500000\n140\n3.500000\n141\n3.500000\n140.500000\n");
+
+        // printf of a double faults if %rsp is not 16-byte aligned at the call, the one stack
+        // argument of `seven` is padded to 16 bytes when nothing else was pushed
+        let main = function(&assembly, "main");
+        let call = main.iter().position(|line| *line == "\tcall\tseven").unwrap();
+        assert!(main[..call].contains(&"\tsubq\t$8, %rsp"), "{:#?}", main);
+        assert!(main[call + 1..].contains(&"\taddq\t$16, %rsp"), "{:#?}", main);
+    }
+
+    #[test]
+    fn test_callee_saved_registers() {
+        // `deep` needs more registers than the caller-saved ones, main keeps values in %rbx and
+        // the others across the call
+        let text = "
+            int deep(int x) {
+                return x * (2 + (3 + (4 + (5 + (6 + (7 + (8 + (9 + (10 + (11 + 12))))))))));
+            }
+            int main() {
+                print 1 + (2 + (3 + (4 + (5 + (6 + (7 + deep(1)))))));
+            }
+        ";
+        let (assembly, output) = run(text);
+        assert_eq!(output, "105\n");
+
+        let deep = function(&assembly, "deep");
+        let saves = deep.iter().filter(|line| line.starts_with("\tmovq\t%rbx, ")).count();
+        let restores = deep.iter().filter(|line| line.starts_with("\tmovq\t") && line.ends_with("(%rbp), %rbx")).count();
+        assert_eq!((saves, restores), (1, 1), "{:#?}", deep);
+    }
+
+    #[test]
+    fn test_long_programs() {
+        // long sums and else-if chains recurse as deep as nested expressions
+        let sum = vec!["1"; 5000].join(" + ");
+        let chain: String = (1..500).map(|i| format!(" else if (a == {}) print {};", i, i)).collect();
+        let text = format!("int main() {{ int a = 499; print {}; if (a == 0) print 0;{} else print -1; }}", sum, chain);
+        let (_, output) = run(&text);
+        assert_eq!(output, "5000\n499\n");
+    }
+
+    #[test]
+    fn test_register_spilling() {
+        // deeper than the 11 general and 15 floating registers, values are spilled to the frame
+        // and reloaded, across calls too
+        let text = "
+            int id(int x) { return x; }
+            double half(double x) { return x / 2; }
+            int main() {
+                int a = 3;
+                print 1 + (2 + (3 + (4 + (5 + (6 + (7 + (8 + (9 + (10 + (11 + (12 + (13 + (14 + (15 + (16 + (17 + (18
+                    + (19 + (20 + (21 + (22 + (23 + (24 + (25 + (26 + (27 + (28 + (29 + (30 + (31 + (32 + (33 + (34
+                    + (35 + (36 + (37 + (38 + (39 + 40))))))))))))))))))))))))))))))))))))));
+                print 1 * (2 * (3 * (4 * (5 * (6 * (7 * (8 * (9 * (10 * (11 * (12 - id(a) * 0)))))))))));
+                print 1.5 + (2.5 + (3.5 + (4.5 + (5.5 + (6.5 + (7.5 + (8.5 + (9.5 + (10.5 + (11.5 + (12.5 + (13.5 + (14.5
+                    + (15.5 + (16.5 + (17.5 + (18.5 + (19.5 + half(41.0)))))))))))))))))));
+                print 1 + (2 + (3 + (4 + (5 + (6 + (7 + (8 + (9 + (10 + (11 + (12 + (1.5 + (2.5 + (3.5 + (4.5 + (5.5
+                    + (6.5 + (7.5 + (8.5 + (9.5 + (10.5 + (11.5 + (12.5 + (13.5 + (14.5 + (15.5
+                    + (16.5 + id(a))))))))))))))))))))))))))));
+            }
+        ";
+        let (assembly, output) = run(text);
+        assert_eq!(output, "820\n479001600\n220.000000\n225.000000\n");
+        // both kinds of registers went to spill slots
+        let main = function(&assembly, "main");
+        assert!(main.iter().any(|line| line.starts_with("\tmovq\t%r") && line.ends_with("(%rbp)")), "{:#?}", main);
+        assert!(main.iter().any(|line| line.starts_with("\tmovsd\t%xmm") && line.ends_with("(%rbp)")), "{:#?}", main);
+
+        // any depth, once the registers are used up every level keeps its left operand in a slot
+        let nested = |term: &str, depth: usize| format!("{}{}{}", format!("{} + (", term).repeat(depth), term, ")".repeat(depth));
+        let text = format!(
+            "int id(int x) {{ return x; }}\nint main() {{ int a = 1; print {}; print {}; print {}; }}",
+            nested("1", 800), nested("0.5", 800), nested("id(a)", 300),
+        );
+        assert_eq!(run(&text).1, "801\n400.500000\n301\n");
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let text = "
+            int main() {
+                int i = -1;
+                unsigned int u = 1;
+                long l = -1;
+                unsigned long ul = 1;
+                print i < 1;
+                print i < u;
+                print u > i;
+                print l <= 0;
+                print l >= ul;
+                unsigned char uc = 255;
+                print uc > i;
+                double zero = 0.0;
+                double nan = zero / zero;
+                double d = 0.5;
+                print d > 0.25;
+                print d >= 0.5;
+                print d < 0.25;
+                print d <= 0.5;
+                print nan == nan;
+                print nan != nan;
+                print nan < d;
+                print nan > d;
+                print nan <= d;
+                print nan >= d;
+            }
+        ";
+        let (assembly, output) = run(text);
+        assert_eq!(output, "1\n0\n0\n1\n1\n1\n1\n1\n0\n1\n0\n1\n0\n0\n0\n0\n");
+        for set in ["setl", "setb", "setle", "setae", "setg", "seta", "setp", "setnp"] {
+            assert!(assembly.contains(&format!("\t{}\t", set)), "{} in {}", set, assembly);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::common::errors::error::CompilerErrorKind;
+use crate::common::span::Span;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+/**
+ A span of source a diagnostic points at, the primary one is where the problem is and
+ secondary ones are related places, e.g. a previous declaration
+ */
+#[derive(Clone, Debug)]
+pub struct Label {
+    pub span: Span,
+    pub message: Option<String>,
+    pub primary: bool,
+}
+
+/**
+ A suggested edit: the text of the span is replaced, an empty span is an insertion. Positioned
+ by its byte offsets only
+ */
+#[derive(Clone, Debug)]
+pub struct FixIt {
+    pub span: Span,
+    pub replacement: String,
+}
+
+/**
+ <p> A message for the user about the source: an error, a warning or a note. </p>
+
+ Codes identify the kind of problem, the hundreds give the stage reporting it:
+ E00 files and the command line, E01 scanner, E02 preprocessor, E03 parser, E04 semantic analysis,
+ E05 code generation, E06 assembling and linking, E09 internal errors. Warnings are numbered the
+ same way with a W.
+ */
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub labels: Vec<Label>,
+    /**
+    Further explanations, without a place in the source
+    */
+    pub notes: Vec<String>,
+    pub fix_its: Vec<FixIt>,
+}
+
+impl Diagnostic {
+
+    pub fn new(severity: Severity, message: String) -> Diagnostic {
+        Self {
+            severity,
+            code: None,
+            message,
+            labels: vec!(),
+            notes: vec!(),
+            fix_its: vec!(),
+        }
+    }
+
+    pub fn error(message: String) -> Diagnostic {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: String) -> Diagnostic {
+        Self::new(Severity::Warning, message)
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Diagnostic {
+        self.code = Some(code);
+        self
+    }
+
+    /**
+    Places the diagnostic at the span
+    */
+    pub fn at(mut self, span: Span) -> Diagnostic {
+        self.labels.retain(|label| !label.primary);
+        self.labels.insert(0, Label {
+            span,
+            message: None,
+            primary: true,
+        });
+        self
+    }
+
+    pub fn with_secondary(mut self, span: Span, message: String) -> Diagnostic {
+        self.labels.push(Label {
+            span,
+            message: Some(message),
+            primary: false,
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: String) -> Diagnostic {
+        self.notes.push(note);
+        self
+    }
+
+    pub fn with_fix_it(mut self, span: Span, replacement: String) -> Diagnostic {
+        self.fix_its.push(FixIt {
+            span,
+            replacement,
+        });
+        self
+    }
+
+    /**
+    Suggests inserting text right after a span
+    */
+    pub fn with_insertion_after(self, span: Span, text: String) -> Diagnostic {
+        let at = Span {
+            start: span.end,
+            ..span
+        };
+        self.with_fix_it(at, text)
+    }
+
+    pub fn primary_span(&self) -> Option<Span> {
+        self.labels.iter().find(|label| label.primary).map(|label| label.span)
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+}
+
+impl From<CompilerErrorKind> for Diagnostic {
+    fn from(error: CompilerErrorKind) -> Diagnostic {
+        let (code, span, message) = match error {
+            CompilerErrorKind::Diagnostic(diagnostic) => return *diagnostic,
+            CompilerErrorKind::FileError(message) => ("E0001", None, message),
+            CompilerErrorKind::ScannerError(span, message) => ("E0100", Some(span), message),
+            CompilerErrorKind::PreprocessorError(span, message) => ("E0200", Some(span), message),
+            CompilerErrorKind::CompilerError(span, message) => ("E0300", Some(span), message),
+            CompilerErrorKind::SemanticError(span, message) => ("E0400", Some(span), message),
+            CompilerErrorKind::CodeGeneratorError(message) => ("E0500", None, message),
+            CompilerErrorKind::InternalError(message) => ("E0900", None, message),
+        };
+        let diagnostic = Diagnostic::error(message).with_code(code);
+        match span {
+            Some(span) => diagnostic.at(span),
+            None => diagnostic,
+        }
+    }
+}
+
+impl From<Diagnostic> for CompilerErrorKind {
+    fn from(diagnostic: Diagnostic) -> CompilerErrorKind {
+        CompilerErrorKind::Diagnostic(Box::new(diagnostic))
+    }
+}
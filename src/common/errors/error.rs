@@ -1,20 +1,52 @@
 use std::fmt::{Debug, Display, Formatter};
+use crate::common::errors::diagnostic::Diagnostic;
+use crate::common::span::Span;
 
 pub enum CompilerErrorKind {
     InternalError(String),
     FileError(String),
     CodeGeneratorError(String),
-    ScannerError(usize, usize, String),
-    CompilerError(usize, usize, String),
+    ScannerError(Span, String),
+    PreprocessorError(Span, String),
+    CompilerError(Span, String),
+    SemanticError(Span, String),
+    /**
+    An error with more to say than a message at a span: notes, other spans, fix-its
+    */
+    Diagnostic(Box<Diagnostic>),
+}
+
+impl CompilerErrorKind {
+
+    /**
+    Where the error is, errors of the later stages may not have a place in the source
+    */
+    pub fn get_span(&self) -> Option<Span> {
+        match self {
+            CompilerErrorKind::ScannerError(span, _)
+            | CompilerErrorKind::PreprocessorError(span, _)
+            | CompilerErrorKind::CompilerError(span, _)
+            | CompilerErrorKind::SemanticError(span, _) => Some(*span),
+            CompilerErrorKind::Diagnostic(diagnostic) => diagnostic.primary_span(),
+            _ => None,
+        }
+    }
+
 }
 
 fn fmt(error: &CompilerErrorKind, f: &mut Formatter<'_>) -> std::fmt::Result {
     match error {
-        CompilerErrorKind::ScannerError(line, column, message) => {
-            write!(f, "Scanner Error (line: {}, column: {}, message: {})", line, column, message)
+        CompilerErrorKind::ScannerError(span, message) => {
+            write!(f, "Scanner Error (line: {}, column: {}, message: {})", span.line, span.column, message)
         },
-        CompilerErrorKind::CompilerError(line, column, message) => {
-            write!(f, "Compiler Error: (line: {}, column: {}, message: {})", line, column, message)
+        CompilerErrorKind::PreprocessorError(span, message) => {
+            write!(f, "Preprocessor Error (line: {}, column: {}, message: {})", span.line, span.column, message)
+        },
+        CompilerErrorKind::CompilerError(span, message) => {
+            write!(f, "Compiler Error: (line: {}, column: {}, message: {})", span.line, span.column, message)
+        },
+        CompilerErrorKind::SemanticError(span, message) => {
+            write!(f, "Semantic Error: (line: {}, column: {}, message: {})", span.line, span.column, message)
         },
         CompilerErrorKind::FileError(message) => {
             write!(f, "File operation Error: {}", message)
@@ -24,7 +56,13 @@ fn fmt(error: &CompilerErrorKind, f: &mut Formatter<'_>) -> std::fmt::Result {
         },
         CompilerErrorKind::InternalError(message) => {
             write!(f, "Internal Error: {}", message)
-        }
+        },
+        CompilerErrorKind::Diagnostic(diagnostic) => {
+            match diagnostic.primary_span() {
+                Some(span) => write!(f, "Error (line: {}, column: {}, message: {})", span.line, span.column, diagnostic.message),
+                None => write!(f, "Error: {}", diagnostic.message),
+            }
+        },
     }
 }
 
@@ -38,4 +76,4 @@ impl Debug for CompilerErrorKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         fmt(self, f)
     }
-}
\ No newline at end of file
+}
@@ -1 +1,3 @@
-pub mod error;
\ No newline at end of file
+pub mod diagnostic;
+pub mod error;
+pub mod renderer;
\ No newline at end of file
@@ -0,0 +1,219 @@
+use std::fmt::Write;
+use crate::common::errors::diagnostic::{Diagnostic, FixIt, Severity};
+use crate::common::source_map::SourceMap;
+use crate::common::span::Span;
+
+/**
+ <p> Renders a diagnostic the way gcc and clang do: the place and the message, then the source
+ line with the span underlined. </p>
+
+ <pre>
+ test.c:3:12: error[E0300]: Expected ';' after expression.
+     3 |     a = b + 1
+       |              ^
+ test.c:1:5: note: previously declared here
+     1 | int a;
+       |     -
+   = help: insert ';'
+     3 |     a = b + 1;
+       |              +
+ </pre>
+ */
+pub fn render(diagnostic: &Diagnostic, source_map: &SourceMap) -> String {
+    let mut output = String::new();
+    let primary = diagnostic.primary_span();
+    header(&mut output, source_map, primary, diagnostic.severity, diagnostic.code, &diagnostic.message);
+    if let Some(span) = primary {
+        snippet(&mut output, source_map, span, '^');
+    }
+
+    for label in diagnostic.labels.iter().filter(|label| !label.primary) {
+        let message = label.message.as_deref().unwrap_or_default();
+        header(&mut output, source_map, Some(label.span), Severity::Note, None, message);
+        snippet(&mut output, source_map, label.span, '-');
+    }
+    for note in diagnostic.notes.iter() {
+        writeln!(output, "  = note: {}", note).unwrap();
+    }
+    for fix_it in diagnostic.fix_its.iter() {
+        suggestion(&mut output, source_map, fix_it);
+    }
+    output
+}
+
+fn header(output: &mut String, source_map: &SourceMap, span: Option<Span>, severity: Severity, code: Option<&str>, message: &str) {
+    if let Some(span) = span {
+        let (line, column) = source_map.line_column(span);
+        write!(output, "{}:{}:{}: ", source_map.presumed_path(span).display(), line, column).unwrap();
+    }
+    match code {
+        Some(code) => writeln!(output, "{}[{}]: {}", severity.name(), code, message).unwrap(),
+        None => writeln!(output, "{}: {}", severity.name(), message).unwrap(),
+    }
+}
+
+/**
+ The source line of a span, and the part of the span on it underlined: `^~~~` for the primary
+ span and `----` for others. Nothing is shown for text that is not kept, e.g. of a name given
+ by `#line`
+ */
+fn snippet(output: &mut String, source_map: &SourceMap, span: Span, marker: char) {
+    let Some((line_start, line)) = source_line(source_map, span) else {
+        return;
+    };
+    let (number, _) = source_map.line_column(span);
+    let width = number.to_string().len();
+
+    let prefix = &line[..span.start - line_start];
+    let underlined = line[span.start - line_start..]
+        .get(..span.end.saturating_sub(span.start))
+        .unwrap_or(&line[span.start - line_start..]);
+    let length = underlined.chars().count().max(1);
+    let underline: String = match marker {
+        '^' => std::iter::once('^').chain(std::iter::repeat_n('~', length - 1)).collect(),
+        other => std::iter::repeat_n(other, length).collect(),
+    };
+
+    writeln!(output, "{:>width$} | {}", number, line).unwrap();
+    writeln!(output, "{:>width$} | {}{}", "", indentation(prefix), underline).unwrap();
+}
+
+/**
+ A fix-it: what to do, and the line as it is after the edit with the new text marked
+ */
+fn suggestion(output: &mut String, source_map: &SourceMap, fix_it: &FixIt) {
+    let action = match (fix_it.span.start == fix_it.span.end, fix_it.replacement.is_empty()) {
+        (true, _) => format!("insert '{}'", fix_it.replacement),
+        (false, true) => String::from("remove this"),
+        (false, false) => format!("replace with '{}'", fix_it.replacement),
+    };
+    writeln!(output, "  = help: {}", action).unwrap();
+
+    let Some((line_start, line)) = source_line(source_map, fix_it.span) else {
+        return;
+    };
+    let start = fix_it.span.start - line_start;
+    // edits are shown on one line
+    let end = (fix_it.span.end - line_start).min(line.len());
+    if fix_it.replacement.is_empty() {
+        return;
+    }
+    let (number, _) = source_map.line_column(fix_it.span);
+    let width = number.to_string().len();
+    let edited = format!("{}{}{}", &line[..start], fix_it.replacement, &line[end..]);
+    let marks: String = std::iter::repeat_n('+', fix_it.replacement.chars().count()).collect();
+
+    writeln!(output, "{:>width$} | {}", number, edited).unwrap();
+    writeln!(output, "{:>width$} | {}{}", "", indentation(&line[..start]), marks).unwrap();
+}
+
+/**
+ Offset and text of the line a span starts on, without the line break
+ */
+fn source_line(source_map: &SourceMap, span: Span) -> Option<(usize, &str)> {
+    let text = source_map.text(span.file);
+    if span.start > text.len() || !text.is_char_boundary(span.start) {
+        return None;
+    }
+    let line_start = text[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[span.start..].find('\n').map_or(text.len(), |i| span.start + i);
+    let line = &text[line_start..line_end];
+    (!line.is_empty() || !text.is_empty()).then_some((line_start, line))
+}
+
+/**
+ Blanks as wide as a text, tabs kept so that the marks line up with the source line
+ */
+fn indentation(text: &str) -> String {
+    text.chars().map(|c| if c == '\t' { '\t' } else { ' ' }).collect()
+}
+
+/**
+ <p> Reports diagnostics on stderr as they come, and counts them. </p>
+ */
+pub struct DiagnosticEmitter {
+    errors: usize,
+    warnings: usize,
+}
+
+impl DiagnosticEmitter {
+
+    pub fn new() -> DiagnosticEmitter {
+        Self {
+            errors: 0,
+            warnings: 0,
+        }
+    }
+
+    pub fn emit(&mut self, diagnostic: &Diagnostic, source_map: &SourceMap) {
+        match diagnostic.severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Note => {},
+        }
+        eprint!("{}", render(diagnostic, source_map));
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    /**
+    Counts of what was reported, e.g. "1 warning and 2 errors generated."
+    */
+    pub fn summary(&self) -> Option<String> {
+        let count = |n: usize, what: &str| format!("{} {}{}", n, what, if n == 1 { "" } else { "s" });
+        let summary = match (self.warnings, self.errors) {
+            (0, 0) => return None,
+            (warnings, 0) => count(warnings, "warning"),
+            (0, errors) => count(errors, "error"),
+            (warnings, errors) => format!("{} and {}", count(warnings, "warning"), count(errors, "error")),
+        };
+        Some(format!("{} generated.", summary))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render() {
+        let mut source_map = SourceMap::new();
+        let text = "int a;\nint main() {\n\ta = b +\n  1\n}\n";
+        let file = source_map.add_file(PathBuf::from("test.c"), String::from(text));
+        let b = text.find('b').unwrap();
+        let plus = text.find('+').unwrap();
+        let one = text.find('1').unwrap();
+
+        let diagnostic = Diagnostic::error(String::from("Expected ';' after expression."))
+            .with_code("E0300")
+            .at(Span::new(file, b, plus + 1, 3, 6))
+            .with_secondary(Span::new(file, 4, 5, 1, 5), String::from("declared here"))
+            .with_note(String::from("statements end with ';'"))
+            .with_insertion_after(Span::new(file, one, one + 1, 4, 3), String::from(";"));
+        assert_eq!(render(&diagnostic, &source_map), "\
+test.c:3:6: error[E0300]: Expected ';' after expression.
+3 | \ta = b +
+  | \t    ^~~
+test.c:1:5: note: declared here
+1 | int a;
+  |     -
+  = note: statements end with ';'
+  = help: insert ';'
+4 |   1;
+  |    +
+");
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut emitter = DiagnosticEmitter::new();
+        assert_eq!(emitter.summary(), None);
+        emitter.warnings = 1;
+        emitter.errors = 2;
+        assert_eq!(emitter.summary().unwrap(), "1 warning and 2 errors generated.");
+    }
+}
@@ -1,5 +1,7 @@
 use crate::common::errors::error::CompilerErrorKind;
 
 pub mod errors;
+pub mod source_map;
+pub mod span;
 
-pub type Result<T> = core::result::Result<T, CompilerErrorKind>;
\ No newline at end of file
+pub type Result<T> = core::result::Result<T, CompilerErrorKind>;
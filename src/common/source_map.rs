@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+use crate::common::span::Span;
+
+/**
+ <p> The files read in a compilation, spans refer to them by index. </p>
+
+ Files renamed or renumbered by `#line` keep their spans physical, the map records the directive
+ and translates spans into the presumed file and line when they are reported.
+ */
+#[derive(Default)]
+pub struct SourceMap {
+    files: Vec<MappedFile>,
+}
+
+struct MappedFile {
+    path: PathBuf,
+    text: String,
+    /**
+    Renumberings by `#line`, in the order of the lines they apply from
+    */
+    line_marks: Vec<LineMark>,
+}
+
+/**
+ From the physical line `from` on, lines are numbered from `line` in the file `presumed_file`
+ */
+struct LineMark {
+    from: usize,
+    line: usize,
+    presumed_file: usize,
+}
+
+impl SourceMap {
+
+    pub fn new() -> SourceMap {
+        Self {
+            files: vec!(),
+        }
+    }
+
+    /**
+    Adds a file and its text, returns its index. Names given by `#line` have no text
+    */
+    pub fn add_file(&mut self, path: PathBuf, text: String) -> usize {
+        self.files.push(MappedFile {
+            path,
+            text,
+            line_marks: vec!(),
+        });
+        self.files.len() - 1
+    }
+
+    pub fn path(&self, file: usize) -> &Path {
+        &self.files[file].path
+    }
+
+    pub fn text(&self, file: usize) -> &str {
+        &self.files[file].text
+    }
+
+    /**
+    Records a `#line` directive: the physical line `from` of `file` is numbered `line`, in
+    `presumed_file` if one is named or the presumed file so far otherwise
+    */
+    pub fn add_line_mark(&mut self, file: usize, from: usize, line: usize, presumed_file: Option<usize>) {
+        let presumed_file = presumed_file.unwrap_or_else(|| self.presumed_file(file, from));
+        self.files[file].line_marks.push(LineMark {
+            from,
+            line,
+            presumed_file,
+        });
+    }
+
+    fn line_mark(&self, file: usize, line: usize) -> Option<&LineMark> {
+        self.files.get(file)?.line_marks.iter().rev().find(|mark| mark.from <= line)
+    }
+
+    fn presumed_file(&self, file: usize, line: usize) -> usize {
+        self.line_mark(file, line).map_or(file, |mark| mark.presumed_file)
+    }
+
+    /**
+    File name a span is reported in
+    */
+    pub fn presumed_path(&self, span: Span) -> &Path {
+        self.path(self.presumed_file(span.file, span.line))
+    }
+
+    /**
+    Line and column a span is reported at
+    */
+    pub fn line_column(&self, span: Span) -> (usize, usize) {
+        match self.line_mark(span.file, span.line) {
+            Some(mark) => (mark.line + (span.line - mark.from), span.column),
+            None => (span.line, span.column),
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_marks() {
+        let mut source_map = SourceMap::new();
+        let file = source_map.add_file(PathBuf::from("a.c"), String::from("x\n#line 10\ny\n#line 1 \"b.c\"\nz"));
+        let renamed = source_map.add_file(PathBuf::from("b.c"), String::new());
+        source_map.add_line_mark(file, 3, 10, None);
+        source_map.add_line_mark(file, 5, 1, Some(renamed));
+
+        let at = |line| Span::new(file, 0, 1, line, 2);
+        assert_eq!((source_map.presumed_path(at(1)), source_map.line_column(at(1))), (Path::new("a.c"), (1, 2)));
+        assert_eq!((source_map.presumed_path(at(4)), source_map.line_column(at(4))), (Path::new("a.c"), (11, 2)));
+        assert_eq!((source_map.presumed_path(at(6)), source_map.line_column(at(6))), (Path::new("b.c"), (2, 2)));
+    }
+}
@@ -0,0 +1,44 @@
+/**
+ <p> Where a token or a construct is in the source: the file it was read from, its byte range and
+ the line and column it starts at. </p>
+
+ Lines and columns are physical ones, counted from 1, `#line` is applied when they are reported.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Span {
+    /**
+    Index of the file in the source map
+    */
+    pub file: usize,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+
+    pub fn new(file: usize, start: usize, end: usize, line: usize, column: usize) -> Span {
+        Self {
+            file,
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+
+    /**
+    Span from the start of this one to the end of another, later one of the same file
+    */
+    pub fn to(&self, other: Span) -> Span {
+        if other.file != self.file || other.end < self.start {
+            return *self;
+        }
+        Span {
+            end: other.end,
+            ..*self
+        }
+    }
+
+}
@@ -0,0 +1,347 @@
+use std::io::Read;
+use std::path::PathBuf;
+use crate::codegen::module::output::FileOutput;
+use crate::codegen::x86::x86_generator::X86Generator;
+use crate::common::errors::diagnostic::Diagnostic;
+use crate::common::errors::error::CompilerErrorKind;
+use crate::common::errors::renderer::DiagnosticEmitter;
+use crate::common::source_map::SourceMap;
+use crate::common::Result;
+use crate::driver::options::{Emit, Input, MacroOption, Options, Output, Stage};
+use crate::driver::toolchain::TempDir;
+use crate::interpreter::ast_interpreter::ASTInterpreter;
+use crate::parser::ast::core::AstNode;
+use crate::parser::parser::Parser;
+use crate::preprocessor::preprocessor::Preprocessor;
+use crate::scanner::tokens::TokenType;
+use crate::semantic::type_checker::TypeChecker;
+use crate::symbols::symbol_table::SymbolTable;
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+
+/**
+ Stack of the thread compiling: parsing and code generation recurse over the nesting of the
+ source, deeply nested expressions and long else-if chains need more than the main thread has
+ */
+pub const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/**
+ <p> Runs the stages over each input in turn, up to the one the options stop after. </p>
+
+ Each input is a translation unit of its own. An input with errors produces no output, the
+ others are still compiled. The program is linked if they all compiled.
+ */
+pub struct Driver {
+    options: Options,
+    emitter: DiagnosticEmitter,
+    /**
+    Where the assembly and objects passed to the system tools are written
+    */
+    temp_dir: Option<TempDir>,
+    /**
+    Objects of the compiled inputs, to be linked
+    */
+    objects: Vec<PathBuf>,
+}
+
+impl Driver {
+
+    pub fn new(options: Options) -> Driver {
+        Self {
+            options,
+            emitter: DiagnosticEmitter::new(),
+            temp_dir: None,
+            objects: vec!(),
+        }
+    }
+
+    /**
+    Compiles every input, returns the exit status
+    */
+    pub fn run(&mut self) -> i32 {
+        let result = self.prepare();
+        self.report(result);
+        if !self.emitter.has_errors() {
+            for (index, input) in self.options.inputs.clone().iter().enumerate() {
+                let mut preprocessor = Preprocessor::new(self.options.include_paths.clone());
+                if let Err(e) = self.compile(index, input, &mut preprocessor) {
+                    self.emitter.emit(&Diagnostic::from(e), preprocessor.source_map());
+                }
+            }
+        }
+        if self.options.stage == Stage::Link && !self.emitter.has_errors() {
+            let result = self.link();
+            self.report(result);
+        }
+        self.temp_dir = None;
+
+        if let Some(summary) = self.emitter.summary() {
+            eprintln!("{}", summary);
+        }
+        if self.emitter.has_errors() { EXIT_FAILURE } else { EXIT_SUCCESS }
+    }
+
+    /**
+    Makes room for the files of the system tools, objects given when not linking are only warned
+    about
+    */
+    fn prepare(&mut self) -> Result<()> {
+        match self.options.stage {
+            Stage::Object | Stage::Link => self.temp_dir = Some(TempDir::new()?),
+            _ => {
+                for object in self.options.objects.iter() {
+                    let message = format!("'{}': linker input file unused because linking not done.", object.display());
+                    self.emitter.emit(&Diagnostic::warning(message).with_code("W0001"), &SourceMap::new());
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn report(&mut self, result: Result<()>) {
+        if let Err(e) = result {
+            self.emitter.emit(&Diagnostic::from(e), &SourceMap::new());
+        }
+    }
+
+    /**
+    One translation unit, the errors reported on the way are emitted as they come
+    */
+    fn compile(&mut self, index: usize, input: &Input, preprocessor: &mut Preprocessor) -> Result<()> {
+        for option in self.options.macros.iter() {
+            match option {
+                MacroOption::Define(definition) => preprocessor.define_macro(definition)?,
+                MacroOption::Undefine(name) => preprocessor.undefine_macro(name)?,
+            }
+        }
+        match input {
+            Input::File(path) => preprocessor.push_file(path)?,
+            Input::Stdin => {
+                let mut text = String::new();
+                std::io::stdin().read_to_string(&mut text)
+                    .map_err(|e| CompilerErrorKind::FileError(format!("Cannot read stdin: {}", e)))?;
+                preprocessor.push_source(input.path(), text)?;
+            },
+        }
+
+        let output = self.options.output_for(input);
+        match self.options.stage {
+            Stage::Preprocess => return self.preprocess(preprocessor, &output),
+            Stage::Emit(Emit::Tokens) => return self.dump_tokens(preprocessor, &output),
+            _ => {},
+        }
+
+        let mut parser = Parser::new(preprocessor);
+        if let Some(error_limit) = self.options.error_limit {
+            parser.set_error_limit(error_limit);
+        }
+        let result = parser.parse();
+        let symbols = parser.take_symbols();
+
+        for warning in preprocessor.take_diagnostics() {
+            self.emitter.emit(&warning, preprocessor.source_map());
+        }
+        let source_map = preprocessor.source_map();
+        let mut ast = match result {
+            Ok(ast) => ast,
+            Err(diagnostics) => {
+                for diagnostic in diagnostics {
+                    self.emitter.emit(&diagnostic, source_map);
+                }
+                return Ok(());
+            },
+        };
+        if self.options.stage == Stage::Emit(Emit::Ast) {
+            return write(&output, &format!("{:#?}\n", ast));
+        }
+
+        if let Err(errors) = TypeChecker::new(&symbols).check(&mut ast) {
+            for error in errors {
+                self.emitter.emit(&Diagnostic::from(error), source_map);
+            }
+            return Ok(());
+        }
+        if self.options.stage == Stage::Emit(Emit::Ir) {
+            return write(&output, &format!("{:#?}\n", ast));
+        }
+        if matches!(self.options.stage, Stage::Emit(Emit::Asm) | Stage::Assemble) {
+            return generate(&*ast, symbols, &output);
+        }
+
+        // numbered, inputs of the same name in different directories are compiled apart
+        let temp_dir = self.temp_dir.as_ref().unwrap().path();
+        let stem = match input {
+            Input::File(path) => path.file_stem().unwrap_or_default().to_string_lossy(),
+            Input::Stdin => "stdin".into(),
+        };
+        let assembly = temp_dir.join(format!("{}-{}.s", index, stem));
+        generate(&*ast, symbols, &Output::File(assembly.clone()))?;
+
+        let object = match (self.options.stage, output) {
+            (Stage::Object, Output::File(path)) => path,
+            _ => temp_dir.join(format!("{}-{}.o", index, stem)),
+        };
+        self.options.toolchain.assemble(&assembly, &object)?;
+        self.objects.push(object);
+        Ok(())
+    }
+
+    /**
+    The objects of the inputs, then those given, into the executable
+    */
+    fn link(&mut self) -> Result<()> {
+        let output = match &self.options.output {
+            Some(Output::File(path)) => path.clone(),
+            _ => PathBuf::from("a.out"),
+        };
+        let objects = [self.objects.as_slice(), self.options.objects.as_slice()].concat();
+        self.options.toolchain.link(&objects, &self.options.link_options, &output)
+    }
+
+    /**
+    `-E`: the tokens after preprocessing, on the lines they come from as far as possible
+    */
+    fn preprocess(&mut self, preprocessor: &mut Preprocessor, output: &Output) -> Result<()> {
+        let mut text = String::new();
+        // the source file, for each inclusion of a header apart, the presumed path and line
+        let mut previous: Option<(usize, String, usize)> = None;
+        loop {
+            let token = match preprocessor.next_token() {
+                Ok(token) => token,
+                Err(e) => {
+                    self.emitter.emit(&Diagnostic::from(e), preprocessor.source_map());
+                    continue;
+                },
+            };
+            if token.is(TokenType::Eof) {
+                break;
+            }
+            let source_map = preprocessor.source_map();
+            let file = token.span().file;
+            let path = source_map.presumed_path(token.span()).display().to_string();
+            let (line, column) = source_map.line_column(token.span());
+            let same_file = |previous: &(usize, String, usize)| previous.0 == file && previous.1 == path;
+
+            match &previous {
+                // tokens of the same line, macro expansions included
+                Some(last) if same_file(last) && line <= last.2 => {
+                    if token.leading_space || token.line_start {
+                        text.push(' ');
+                    }
+                },
+                // a few blank lines keep the following lines where they were, a line marker is
+                // shorter for more, or for another file
+                Some(last) if same_file(last) && line - last.2 <= 8 => {
+                    text.push_str(&"\n".repeat(line - last.2));
+                    text.push_str(&" ".repeat(column - 1));
+                },
+                _ => {
+                    if previous.is_some() {
+                        text.push('\n');
+                    }
+                    text.push_str(&format!("# {} \"{}\"\n", line, path));
+                    text.push_str(&" ".repeat(column - 1));
+                },
+            }
+            text.push_str(&token.spelling);
+            if previous.as_ref().is_none_or(|last| !same_file(last) || line > last.2) {
+                previous = Some((file, path, line));
+            }
+        }
+        text.push('\n');
+        for warning in preprocessor.take_diagnostics() {
+            self.emitter.emit(&warning, preprocessor.source_map());
+        }
+        write(output, &text)
+    }
+
+    /**
+    `--emit=tokens`: the tokens the parser gets, one per line with its place and spelling
+    */
+    fn dump_tokens(&mut self, preprocessor: &mut Preprocessor, output: &Output) -> Result<()> {
+        let mut text = String::new();
+        loop {
+            let token = match preprocessor.next_token() {
+                Ok(token) => token,
+                Err(e) => {
+                    self.emitter.emit(&Diagnostic::from(e), preprocessor.source_map());
+                    continue;
+                },
+            };
+            let source_map = preprocessor.source_map();
+            let (line, column) = source_map.line_column(token.span());
+            text.push_str(&format!(
+                "{}:{}:{}\t{:?}\t{}\n",
+                source_map.presumed_path(token.span()).display(),
+                line,
+                column,
+                token.token.get_token_type(),
+                token.spelling,
+            ));
+            if token.is(TokenType::Eof) {
+                break;
+            }
+        }
+        write(output, &text)
+    }
+
+}
+
+/**
+ The assembly of a checked AST
+ */
+fn generate(ast: &dyn AstNode, symbols: SymbolTable, output: &Output) -> Result<()> {
+    let mut file = open(output)?;
+    let code_generator = Box::new(X86Generator::new(&mut file));
+    let mut interpreter = ASTInterpreter::new(code_generator, symbols);
+    let result = interpreter.interpret(ast).and_then(|_| file.flush());
+    // no half-written assembly is left behind
+    if let (Err(_), Output::File(path)) = (&result, output) {
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+fn open(output: &Output) -> Result<FileOutput> {
+    match output {
+        Output::File(path) => FileOutput::new(path),
+        Output::Stdout => Ok(FileOutput::stdout()),
+    }
+}
+
+fn write(output: &Output, text: &str) -> Result<()> {
+    let mut file = open(output)?;
+    file.write(text)?;
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(args: &[String]) -> i32 {
+        Driver::new(Options::parse(args).unwrap()).run()
+    }
+
+    #[test]
+    fn test_preprocessed_input() {
+        let dir = TempDir::new().unwrap();
+        let path = |name: &str| dir.path().join(name).display().to_string();
+        std::fs::write(path("inc.h"), "int twice(int x);\n").unwrap();
+        let text = "#include \"inc.h\"\n#include \"inc.h\"\nint main() {\n  return twice(2);\n}\nint twice(int x) { return 2 * x; }\n";
+        std::fs::write(path("a.c"), text).unwrap();
+
+        // each inclusion on lines of its own
+        assert_eq!(run(&[String::from("-E"), path("a.c"), String::from("-o"), path("a.i")]), EXIT_SUCCESS);
+        let preprocessed = std::fs::read_to_string(path("a.i")).unwrap();
+        let marker = format!("# 1 \"{}\"\nint twice(int x);\n", path("inc.h"));
+        assert!(preprocessed.starts_with(&marker.repeat(2)), "{}", preprocessed);
+
+        // the line markers are read back, to the same code
+        assert_eq!(run(&[String::from("-S"), path("a.i"), String::from("-o"), path("a.s")]), EXIT_SUCCESS);
+        assert_eq!(run(&[String::from("-S"), path("a.c"), String::from("-o"), path("b.s")]), EXIT_SUCCESS);
+        assert_eq!(std::fs::read_to_string(path("a.s")).unwrap(), std::fs::read_to_string(path("b.s")).unwrap());
+    }
+}
@@ -0,0 +1,279 @@
+use std::path::{Path, PathBuf};
+use crate::common::errors::diagnostic::Diagnostic;
+use crate::common::Result;
+use crate::driver::toolchain::Toolchain;
+
+pub const USAGE: &str = "\
+Usage: c-compiler [options] <file>...
+
+A file named - is read from stdin. Files ending in .o, .a or .so are passed to the linker.
+
+Options:
+  -o <file>             Write the output to <file>, - for stdout
+  -E                    Preprocess only
+  -S                    Compile to assembly only
+  -c                    Compile and assemble, but do not link
+  --emit=<kind>         Write tokens, ast, ir (the checked AST) or asm, and stop
+  -I <dir>              Add <dir> to the directories searched for #include files
+  -D <name>[=<value>]   Define a macro, as 1 if no value is given
+  -U <name>             Undefine a macro
+  -fmax-errors=<n>      Stop parsing after <n> errors, 0 for no limit
+  -L <dir>              Add <dir> to the directories searched for libraries
+  -l <library>          Link with <library>
+  --assembler=<path>    Assemble with <path>, by default $AS or as
+  --linker=<path>       Link with <path>, by default $CC or cc
+  -h, --help            Print this message
+
+Exit status: 0 on success, 1 if errors were reported, 2 for a bad command line.
+";
+
+/**
+ What `--emit` writes: a stage's result, as it is passed on to the next stage
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Emit {
+    Tokens,
+    Ast,
+    /**
+    The AST after semantic analysis, with the implicit conversions made explicit: what code
+    generation works from
+    */
+    Ir,
+    Asm,
+}
+
+/**
+ The stage the compilation stops after
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stage {
+    Preprocess,
+    Emit(Emit),
+    Assemble,
+    Object,
+    Link,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MacroOption {
+    Define(String),
+    Undefine(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+
+    /**
+    Name of the input in messages and `__FILE__`
+    */
+    pub fn path(&self) -> &Path {
+        match self {
+            Input::File(path) => path,
+            Input::Stdin => Path::new("<stdin>"),
+        }
+    }
+
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Output {
+    File(PathBuf),
+    Stdout,
+}
+
+/**
+ <p> The command line: the inputs and what to do with them. </p>
+
+ Options may come before or after the inputs, the ones taking a value take it attached
+ (`-Idir`) or as the next argument (`-I dir`).
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    /**
+    The sources to compile
+    */
+    pub inputs: Vec<Input>,
+    /**
+    Objects and libraries given as files, linked after the compiled sources
+    */
+    pub objects: Vec<PathBuf>,
+    pub output: Option<Output>,
+    pub stage: Stage,
+    pub include_paths: Vec<PathBuf>,
+    /**
+    `-D` and `-U` in the order given, a later one overrides an earlier one
+    */
+    pub macros: Vec<MacroOption>,
+    pub error_limit: Option<usize>,
+    /**
+    `-L` and `-l` in the order given, passed to the linker after the objects
+    */
+    pub link_options: Vec<String>,
+    pub toolchain: Toolchain,
+    pub help: bool,
+}
+
+impl Options {
+
+    pub fn parse(args: &[String]) -> Result<Options> {
+        let mut options = Options {
+            inputs: vec!(),
+            objects: vec!(),
+            output: None,
+            stage: Stage::Link,
+            include_paths: vec!(),
+            macros: vec!(),
+            error_limit: None,
+            link_options: vec!(),
+            toolchain: Toolchain::from_env(),
+            help: false,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |option: &str| -> Result<String> {
+                match arg.strip_prefix(option).filter(|value| !value.is_empty()) {
+                    Some(value) => Ok(value.to_string()),
+                    None => args.next().cloned().ok_or_else(|| error(format!("Missing argument to '{}'.", option))),
+                }
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "-" => options.inputs.push(Input::Stdin),
+                "-E" => options.stage = Stage::Preprocess,
+                "-S" => options.stage = Stage::Assemble,
+                "-c" => options.stage = Stage::Object,
+                _ if arg.starts_with("-o") => {
+                    let output = value("-o")?;
+                    options.output = Some(match output.as_str() {
+                        "-" => Output::Stdout,
+                        _ => Output::File(PathBuf::from(output)),
+                    });
+                },
+                _ if arg.starts_with("-I") => options.include_paths.push(PathBuf::from(value("-I")?)),
+                _ if arg.starts_with("-D") => options.macros.push(MacroOption::Define(value("-D")?)),
+                _ if arg.starts_with("-U") => options.macros.push(MacroOption::Undefine(value("-U")?)),
+                _ if arg.starts_with("-L") => options.link_options.push(format!("-L{}", value("-L")?)),
+                _ if arg.starts_with("-l") => options.link_options.push(format!("-l{}", value("-l")?)),
+                _ if arg.starts_with("--assembler=") => options.toolchain.assembler = PathBuf::from(&arg["--assembler=".len()..]),
+                _ if arg.starts_with("--linker=") => options.toolchain.linker = PathBuf::from(&arg["--linker=".len()..]),
+                _ if arg.starts_with("--emit=") => {
+                    let emit = match &arg["--emit=".len()..] {
+                        "tokens" => Emit::Tokens,
+                        "ast" => Emit::Ast,
+                        "ir" => Emit::Ir,
+                        "asm" => Emit::Asm,
+                        other => return Err(error(format!("Unknown kind '{}' to --emit, expected tokens, ast, ir or asm.", other))),
+                    };
+                    options.stage = Stage::Emit(emit);
+                },
+                _ if arg.starts_with("-fmax-errors=") => {
+                    let limit = &arg["-fmax-errors=".len()..];
+                    let limit = limit.parse()
+                        .map_err(|_| error(format!("Invalid argument '{}' to -fmax-errors.", limit)))?;
+                    options.error_limit = Some(limit);
+                },
+                _ if arg.starts_with('-') => return Err(error(format!("Unknown option '{}'.", arg))),
+                _ if is_object(Path::new(arg)) => options.objects.push(PathBuf::from(arg)),
+                _ => options.inputs.push(Input::File(PathBuf::from(arg))),
+            }
+        }
+
+        if options.help {
+            return Ok(options);
+        }
+        if options.inputs.is_empty() && options.objects.is_empty() {
+            return Err(error(String::from("No input files.")));
+        }
+        if matches!(options.stage, Stage::Object | Stage::Link) && options.output == Some(Output::Stdout) {
+            return Err(error(String::from("Cannot write object files or executables to stdout.")));
+        }
+        // one output file for the output of each input
+        if options.inputs.len() > 1 && options.stage != Stage::Link && matches!(options.output, Some(Output::File(_))) {
+            return Err(error(String::from("Cannot specify '-o' with '-c', '-S', '-E' or '--emit' with multiple files.")));
+        }
+        Ok(options)
+    }
+
+    /**
+    Where the output for an input goes: dumps and preprocessed text to stdout, assembly and
+    objects in the current directory named after the input, the executable to `a.out`. The
+    assembly of stdin goes to stdout, its object to `-.o` as with gcc
+    */
+    pub fn output_for(&self, input: &Input) -> Output {
+        if let Some(output) = &self.output {
+            return output.clone();
+        }
+        let extension = match self.stage {
+            Stage::Preprocess | Stage::Emit(Emit::Tokens | Emit::Ast | Emit::Ir) => return Output::Stdout,
+            Stage::Link => return Output::File(PathBuf::from("a.out")),
+            Stage::Emit(Emit::Asm) | Stage::Assemble => "s",
+            Stage::Object => "o",
+        };
+        match input {
+            Input::File(path) => Output::File(PathBuf::from(path.file_name().unwrap_or_default()).with_extension(extension)),
+            Input::Stdin if self.stage == Stage::Object => Output::File(PathBuf::from("-.o")),
+            Input::Stdin => Output::Stdout,
+        }
+    }
+
+}
+
+fn is_object(path: &Path) -> bool {
+    matches!(path.extension().and_then(|extension| extension.to_str()), Some("o" | "a" | "so"))
+}
+
+fn error(message: String) -> crate::common::errors::error::CompilerErrorKind {
+    Diagnostic::error(message).with_code("E0002").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        Options::parse(&args)
+    }
+
+    #[test]
+    fn test_options() {
+        let error = parse("-S a.c - -o out.s").unwrap_err();
+        assert_eq!(Diagnostic::from(error).code, Some("E0002"));
+
+        let options = parse("-S a.c -I inc -Iother -DX=1 -U X -fmax-errors=3").unwrap();
+        assert_eq!(options.inputs, vec!(Input::File(PathBuf::from("a.c"))));
+        assert_eq!(options.stage, Stage::Assemble);
+        assert_eq!(options.include_paths, vec!(PathBuf::from("inc"), PathBuf::from("other")));
+        assert_eq!(options.macros, vec!(MacroOption::Define(String::from("X=1")), MacroOption::Undefine(String::from("X"))));
+        assert_eq!(options.error_limit, Some(3));
+        assert_eq!(options.output_for(&options.inputs[0]), Output::File(PathBuf::from("a.s")));
+
+        let options = parse("--emit=ast - -o -").unwrap();
+        assert_eq!((options.stage, options.output_for(&Input::Stdin)), (Stage::Emit(Emit::Ast), Output::Stdout));
+        assert_eq!(parse("dir/b.c -c").unwrap().output_for(&Input::File(PathBuf::from("dir/b.c"))), Output::File(PathBuf::from("b.o")));
+
+        let options = parse("a.c b.o -L lib -lm libc.a --linker=ld --assembler=/usr/bin/as").unwrap();
+        assert_eq!((options.inputs.len(), options.objects.clone()), (1, vec!(PathBuf::from("b.o"), PathBuf::from("libc.a"))));
+        assert_eq!(options.link_options, vec!(String::from("-Llib"), String::from("-lm")));
+        assert_eq!(options.toolchain, Toolchain { assembler: PathBuf::from("/usr/bin/as"), linker: PathBuf::from("ld") });
+        assert_eq!(options.output_for(&options.inputs[0]), Output::File(PathBuf::from("a.out")));
+    }
+
+    #[test]
+    fn test_bad_options() {
+        let message = |args: &str| Diagnostic::from(parse(args).unwrap_err()).message;
+        assert_eq!(message(""), "No input files.");
+        assert_eq!(message("a.c -o"), "Missing argument to '-o'.");
+        assert_eq!(message("a.c -x"), "Unknown option '-x'.");
+        assert_eq!(message("a.c --emit=llvm"), "Unknown kind 'llvm' to --emit, expected tokens, ast, ir or asm.");
+        assert_eq!(message("a.c -fmax-errors=x"), "Invalid argument 'x' to -fmax-errors.");
+        assert_eq!(message("-c a.c -o -"), "Cannot write object files or executables to stdout.");
+        assert!(parse("--help").unwrap().help);
+    }
+}
@@ -0,0 +1,153 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::common::errors::diagnostic::Diagnostic;
+use crate::common::errors::error::CompilerErrorKind;
+use crate::common::Result;
+
+/**
+ <p> The system tools turning assembly into a program: an assembler taking GNU syntax, and a
+ linker. </p>
+
+ The linker is run the way `cc` is: given the objects, `-L` and `-l` options and `-o`. With `cc`
+ the C library and startup files are linked in, which the code generated calls into.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toolchain {
+    pub assembler: PathBuf,
+    pub linker: PathBuf,
+}
+
+impl Toolchain {
+
+    /**
+    The tools named by the `AS` and `CC` environment variables, `as` and `cc` by default
+    */
+    pub fn from_env() -> Toolchain {
+        let tool = |variable: &str, default: &str| {
+            std::env::var_os(variable)
+                .filter(|value| !value.is_empty())
+                .map_or_else(|| PathBuf::from(default), PathBuf::from)
+        };
+        Self {
+            assembler: tool("AS", "as"),
+            linker: tool("CC", "cc"),
+        }
+    }
+
+    pub fn assemble(&self, source: &Path, object: &Path) -> Result<()> {
+        let mut command = Command::new(&self.assembler);
+        command.arg("-o").arg(object).arg(source);
+        run(command, "Assembler")
+    }
+
+    pub fn link(&self, objects: &[PathBuf], link_options: &[String], output: &Path) -> Result<()> {
+        let mut command = Command::new(&self.linker);
+        command.arg("-o").arg(output).args(objects).args(link_options);
+        run(command, "Linker")
+    }
+
+}
+
+/**
+ Runs a tool to completion, what it printed is passed on as notes if it fails
+ */
+fn run(mut command: Command, tool: &str) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command.output().map_err(|e| {
+        error(format!("Cannot run {} '{}': {}", tool.to_lowercase(), program, e), "E0601")
+    })?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let line = std::iter::once(program.clone())
+        .chain(command.get_args().map(|arg| arg.to_string_lossy().into_owned()))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let mut diagnostic = Diagnostic::error(format!("{} '{}' failed ({}).", tool, program, output.status))
+        .with_code("E0602")
+        .with_note(format!("Command: {}", line));
+    let printed = [output.stderr, output.stdout].concat();
+    for message in String::from_utf8_lossy(&printed).lines().filter(|line| !line.trim().is_empty()) {
+        diagnostic = diagnostic.with_note(message.to_string());
+    }
+    Err(diagnostic.into())
+}
+
+fn error(message: String, code: &'static str) -> CompilerErrorKind {
+    Diagnostic::error(message).with_code(code).into()
+}
+
+/**
+ A directory of its own for the files passed between the tools, removed with what is in it
+ when dropped
+ */
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+
+    pub fn new() -> Result<TempDir> {
+        let base = std::env::temp_dir();
+        for attempt in 0.. {
+            let path = base.join(format!("c-compiler-{}-{}", std::process::id(), attempt));
+            match std::fs::create_dir(&path) {
+                Ok(()) => return Ok(Self { path }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => {
+                    let message = format!("Cannot create a temporary directory in '{}': {}", base.display(), e);
+                    return Err(CompilerErrorKind::FileError(message));
+                },
+            }
+        }
+        unreachable!()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_failures() {
+        let missing = Toolchain {
+            assembler: PathBuf::from("/nonexistent/as"),
+            linker: PathBuf::from("/nonexistent/cc"),
+        };
+        let diagnostic = Diagnostic::from(missing.assemble(Path::new("a.s"), Path::new("a.o")).unwrap_err());
+        assert_eq!(diagnostic.code, Some("E0601"));
+        assert!(diagnostic.message.starts_with("Cannot run assembler '/nonexistent/as'"));
+
+        // `false` fails without a word, the command is still noted
+        let failing = Toolchain {
+            assembler: PathBuf::from("false"),
+            linker: PathBuf::from("false"),
+        };
+        let diagnostic = Diagnostic::from(failing.link(&[PathBuf::from("a.o")], &[String::from("-lm")], Path::new("a.out")).unwrap_err());
+        assert_eq!(diagnostic.code, Some("E0602"));
+        assert_eq!(diagnostic.notes, vec!(String::from("Command: false -o a.out a.o -lm")));
+    }
+
+    #[test]
+    fn test_temp_dir() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_path_buf();
+        std::fs::write(path.join("a.s"), "").unwrap();
+        let other = TempDir::new().unwrap();
+        assert_ne!(other.path(), path);
+        drop(dir);
+        assert!(!path.exists());
+    }
+}
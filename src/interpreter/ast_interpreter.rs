@@ -1,23 +1,63 @@
+use crate::common::errors::error::CompilerErrorKind;
 use crate::common::Result;
-use crate::codegen::core::CodeGen;
+use crate::codegen::core::{CodeGen, CompareOp};
+use crate::parser::ast::assignment_node::AssignmentNode;
 use crate::parser::ast::binary_op_node::BinaryAstNode;
 use crate::parser::ast::block_node::BlockNode;
+use crate::parser::ast::call_node::CallNode;
+use crate::parser::ast::cast_node::CastNode;
 use crate::parser::ast::core::AstNode;
+use crate::parser::ast::declaration_node::DeclarationNode;
+use crate::parser::ast::expression_statement_node::ExpressionStatementNode;
+use crate::parser::ast::float_value_node::FloatValueNode;
+use crate::parser::ast::identifier_node::IdentifierNode;
+use crate::parser::ast::do_while_node::DoWhileNode;
+use crate::parser::ast::for_node::ForNode;
+use crate::parser::ast::function_node::FunctionNode;
+use crate::parser::ast::if_node::IfNode;
+use crate::parser::ast::loop_control_node::LoopControlNode;
+use crate::parser::ast::while_node::WhileNode;
+use crate::parser::ast::logical_op_node::LogicalAstNode;
 use crate::parser::ast::print_node::PrintAstNode;
+use crate::parser::ast::program_node::ProgramNode;
+use crate::parser::ast::return_node::ReturnNode;
+use crate::parser::ast::string_node::StringNode;
+use crate::parser::ast::unary_op_node::UnaryAstNode;
 use crate::parser::ast::value_node::ValueNode;
+use crate::parser::data_type::DataType;
 use crate::parser::operations::Operations;
+use crate::symbols::symbol_table::{SymbolId, SymbolTable};
+
+struct LoopLabels {
+    continue_label: usize,
+    break_label: usize,
+}
 
 pub struct ASTInterpreter<T>
 where T: CodeGen
 {
-    generator: Box<T>
+    generator: Box<T>,
+
+    /**
+    Jump targets of the enclosing loops, innermost last
+    */
+    loops: Vec<LoopLabels>,
+
+    /**
+    Symbols resolved by the parser, stack offsets are recorded as variables get their slots
+    */
+    symbols: SymbolTable,
 }
 
 impl<T> ASTInterpreter<T>
 where T: CodeGen
 {
-    pub fn new(generator: Box<T>) -> ASTInterpreter<T> {
-        ASTInterpreter { generator }
+    pub fn new(generator: Box<T>, symbols: SymbolTable) -> ASTInterpreter<T> {
+        ASTInterpreter {
+            generator,
+            loops: Vec::new(),
+            symbols,
+        }
     }
 
     pub fn interpret(&mut self, ast: &dyn AstNode) -> Result<()> {
@@ -31,43 +71,528 @@ where T: CodeGen
 
     fn do_interpret(&mut self, ast: &dyn AstNode) -> Result<usize>
     {
-        let operator = ast.get_op();
-
-        if let Some(binary_node) = ast.downcast_ref::<BinaryAstNode>() {
-            let reg1 = self.do_interpret(&*binary_node.left)?;
-            let reg2 = self.do_interpret(&*binary_node.right)?;
-
-            match operator {
-                Operations::OpIntAdd => {
-                    self.generator.cg_add(reg1, reg2)
-                },
-                Operations::OpIntMinus => {
-                    self.generator.cg_sub(reg1, reg2)
-                },
-                Operations::OpIntMul => {
-                    self.generator.cg_mul(reg1, reg2)
-                },
-                Operations::OpIntDiv => {
-                    self.generator.cg_div(reg1, reg2)
-                },
-                other => unimplemented!("{:?}", other),
-            }
-
+        // one helper per kind of node: this recurses for every operand and statement, its frame
+        // is kept small for deeply nested expressions and long else-if chains
+        if let Some(program_node) = ast.downcast_ref::<ProgramNode>() {
+            self.program(program_node).map(|_| 0)
+        } else if let Some(function_node) = ast.downcast_ref::<FunctionNode>() {
+            self.function(function_node).map(|_| 0)
+        } else if let Some(call_node) = ast.downcast_ref::<CallNode>() {
+            self.call(call_node)
+        } else if let Some(return_node) = ast.downcast_ref::<ReturnNode>() {
+            self.return_statement(return_node).map(|_| 0)
+        } else if let Some(binary_node) = ast.downcast_ref::<BinaryAstNode>() {
+            self.binary(binary_node)
+        } else if let Some(logical_node) = ast.downcast_ref::<LogicalAstNode>() {
+            self.logical(logical_node)
+        } else if let Some(unary_node) = ast.downcast_ref::<UnaryAstNode>() {
+            self.unary(unary_node)
+        } else if let Some(assignment_node) = ast.downcast_ref::<AssignmentNode>() {
+            self.assignment(assignment_node)
+        } else if let Some(cast_node) = ast.downcast_ref::<CastNode>() {
+            self.cast(cast_node)
         } else if let Some(value_node) = ast.downcast_ref::<ValueNode>() {
             self.generator.cg_load(value_node.get_value())
+        } else if let Some(float_value_node) = ast.downcast_ref::<FloatValueNode>() {
+            self.generator.cg_load_float(float_value_node.get_value(), float_value_node.get_type())
+        } else if let Some(string_node) = ast.downcast_ref::<StringNode>() {
+            self.generator.cg_load_string(string_node.get_value())
+        } else if let Some(identifier_node) = ast.downcast_ref::<IdentifierNode>() {
+            self.load_variable(identifier_node.symbol)
+        } else if let Some(declaration_node) = ast.downcast_ref::<DeclarationNode>() {
+            self.declaration(declaration_node).map(|_| 0)
+        } else if let Some(statement_node) = ast.downcast_ref::<ExpressionStatementNode>() {
+            self.expression_statement(statement_node).map(|_| 0)
         } else if let Some(block_node) = ast.downcast_ref::<BlockNode>() {
-            for ast in block_node.block.iter() {
-                self.do_interpret(&**ast)?;
-            }
-            Ok(0)
+            self.block(block_node).map(|_| 0)
+        } else if let Some(if_node) = ast.downcast_ref::<IfNode>() {
+            self.if_statement(if_node).map(|_| 0)
+        } else if let Some(while_node) = ast.downcast_ref::<WhileNode>() {
+            self.while_loop(while_node).map(|_| 0)
+        } else if let Some(do_while_node) = ast.downcast_ref::<DoWhileNode>() {
+            self.do_while_loop(do_while_node).map(|_| 0)
+        } else if let Some(for_node) = ast.downcast_ref::<ForNode>() {
+            self.for_loop(for_node).map(|_| 0)
+        } else if let Some(loop_control_node) = ast.downcast_ref::<LoopControlNode>() {
+            self.loop_control(loop_control_node).map(|_| 0)
         } else if let Some(print_node) = ast.downcast_ref::<PrintAstNode>() {
+            self.print(print_node)
+        } else {
+            panic!("Unknown node type");
+        }
+    }
+
+    #[inline(never)]
+    fn program(&mut self, program_node: &ProgramNode) -> Result<()> {
+        for declaration in program_node.declarations.iter() {
+            self.do_interpret(&**declaration)?;
+        }
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn block(&mut self, block_node: &BlockNode) -> Result<()> {
+        for ast in block_node.block.iter() {
+            self.do_interpret(&**ast)?;
+        }
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn expression_statement(&mut self, statement_node: &ExpressionStatementNode) -> Result<()> {
+        let reg = self.do_interpret(&*statement_node.expr)?;
+        self.generator.cg_free_register(reg);
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn cast(&mut self, cast_node: &CastNode) -> Result<usize> {
+        let reg = self.do_interpret(&*cast_node.operand)?;
+        self.generator.cg_cast(reg, cast_node.operand.get_type(), cast_node.get_type())
+    }
+
+    #[inline(never)]
+    fn loop_control(&mut self, loop_control_node: &LoopControlNode) -> Result<()> {
+        let operator = loop_control_node.get_op();
+        let Some(labels) = self.loops.last() else {
+            return Err(CompilerErrorKind::InternalError(
+                format!("{:?} outside of a loop", operator)
+            ));
+        };
+        match operator {
+            Operations::OpBreak => self.generator.cg_jump(labels.break_label),
+            Operations::OpContinue => self.generator.cg_jump(labels.continue_label),
+            other => unimplemented!("{:?}", other),
+        }
+    }
 
-            let reg = self.do_interpret(&*print_node.expr)?;
-            self.generator.cg_printreg(reg)?;
-            Ok(reg)
+    #[inline(never)]
+    fn call(&mut self, call_node: &CallNode) -> Result<usize> {
+        let return_type = self.symbols.get(call_node.symbol).data_type;
 
+        let arg_types: Vec<DataType> = call_node.args.iter()
+            .map(|arg| arg.get_type())
+            .collect();
+        for index in self.generator.cg_call_prepare(&arg_types)? {
+            let reg = self.do_interpret(&*call_node.args[index])?;
+            self.generator.cg_push(reg)?;
+            self.generator.cg_free_register(reg);
+        }
+        self.generator.cg_call(&call_node.name, return_type)
+    }
+
+    #[inline(never)]
+    fn return_statement(&mut self, return_node: &ReturnNode) -> Result<()> {
+        let reg = match &return_node.value {
+            Some(value) => Some(self.do_interpret(&**value)?),
+            None => None,
+        };
+        self.generator.cg_return(reg)
+    }
+
+    #[inline(never)]
+    fn binary(&mut self, binary_node: &BinaryAstNode) -> Result<usize> {
+        let operator = binary_node.get_op();
+        let reg1 = self.do_interpret(&*binary_node.left)?;
+        // keep the left value on the stack if the right hand side may run out of registers
+        let spilled = if self.generator.cg_registers_low() {
+            Some(self.generator.cg_spill(reg1)?)
         } else {
-            panic!("Unknown node type");
+            None
+        };
+        let reg2 = self.do_interpret(&*binary_node.right)?;
+        let reg1 = match spilled {
+            Some(slot) => self.generator.cg_reload(slot)?,
+            None => reg1,
+        };
+
+        self.binary_operation(operator, reg1, reg2, binary_node.left.get_type())
+    }
+
+    #[inline(never)]
+    fn logical(&mut self, logical_node: &LogicalAstNode) -> Result<usize> {
+        let operator = logical_node.get_op();
+        // the left hand side alone decides the result when it is 0 for && or 1 for ||,
+        // in that case the right hand side is skipped
+        let end_label = self.generator.cg_new_label();
+
+        let reg = self.do_interpret(&*logical_node.left)?;
+        let reg = self.generator.cg_to_bool(reg)?;
+        match operator {
+            Operations::OpLogicalAnd => self.generator.cg_jump_if_zero(reg, end_label)?,
+            Operations::OpLogicalOr => self.generator.cg_jump_if_not_zero(reg, end_label)?,
+            other => unimplemented!("{:?}", other),
+        }
+        // otherwise the result is the right hand side alone, it only has to end up
+        // in the same register
+        self.generator.cg_free_register(reg);
+
+        let right = self.do_interpret(&*logical_node.right)?;
+        let right = self.generator.cg_to_bool(right)?;
+        let reg = self.generator.cg_move(right, reg)?;
+
+        self.generator.cg_label(end_label)?;
+        Ok(reg)
+    }
+
+    #[inline(never)]
+    fn unary(&mut self, unary_node: &UnaryAstNode) -> Result<usize> {
+        let operator = unary_node.get_op();
+        if let Some(reg) = self.increment(operator, &*unary_node.operand)? {
+            return Ok(reg);
+        }
+
+        let reg = self.do_interpret(&*unary_node.operand)?;
+
+        let reg = match operator {
+            Operations::OpLogicalNot => self.generator.cg_not(reg)?,
+            Operations::OpUnaryPlus => reg,
+            Operations::OpNegate => self.generator.cg_neg(reg)?,
+            Operations::OpBitNot => self.generator.cg_bitnot(reg)?,
+            Operations::OpFloatNegate => self.generator.cg_fneg(reg, unary_node.get_type())?,
+            other => unimplemented!("{:?}", other),
+        };
+        self.generator.cg_wrap(reg, unary_node.get_type())
+    }
+
+    #[inline(never)]
+    fn assignment(&mut self, assignment_node: &AssignmentNode) -> Result<usize> {
+        let operator = assignment_node.get_op();
+        let Some(target) = assignment_node.target.downcast_ref::<IdentifierNode>() else {
+            return Err(CompilerErrorKind::InternalError(
+                format!("Unsupported assignment target {:?}", assignment_node.target)
+            ));
+        };
+
+        let value = self.do_interpret(&*assignment_node.value)?;
+        let reg = match operator {
+            Operations::OpAssign => value,
+            // compound assignment: load, operate in the type of the value, then store back
+            other => {
+                let target_type = assignment_node.get_type();
+                let value_type = assignment_node.value.get_type();
+
+                let current = self.load_variable(target.symbol)?;
+                let current = self.generator.cg_cast(current, target_type, value_type)?;
+                let result = self.binary_operation(other, current, value, value_type)?;
+                self.generator.cg_cast(result, value_type, target_type)?
+            }
+        };
+        self.store_variable(reg, target.symbol)
+    }
+
+    #[inline(never)]
+    fn print(&mut self, print_node: &PrintAstNode) -> Result<usize> {
+        let reg = self.do_interpret(&*print_node.expr)?;
+        self.generator.cg_printreg(reg, print_node.expr.get_type())?;
+        Ok(reg)
+    }
+
+    #[inline(never)]
+    fn function(&mut self, function_node: &FunctionNode) -> Result<()> {
+        let Some(body) = &function_node.body else {
+            // prototypes only declare the function
+            return Ok(());
+        };
+
+        self.generator.cg_function_preamble(&function_node.name)?;
+        let param_types: Vec<DataType> = function_node.params.iter()
+            .map(|param| param.data_type)
+            .collect();
+        let offsets = self.generator.cg_declare_parameters(&param_types)?;
+        for (param, offset) in function_node.params.iter().zip(offsets) {
+            self.symbols.get_mut(param.symbol).offset = Some(offset);
+        }
+        self.do_interpret(&**body)?;
+        self.generator.cg_function_postamble()?;
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn declaration(&mut self, declaration_node: &DeclarationNode) -> Result<()> {
+        for declarator in declaration_node.declarators.iter() {
+            let offset = self.generator.cg_declare_local(declarator.data_type)?;
+            self.symbols.get_mut(declarator.symbol).offset = Some(offset);
+            if let Some(init) = &declarator.init {
+                let reg = self.do_interpret(&**init)?;
+                let reg = self.store_variable(reg, declarator.symbol)?;
+                self.generator.cg_free_register(reg);
+            }
+        }
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn if_statement(&mut self, if_node: &IfNode) -> Result<()> {
+        let else_label = self.generator.cg_new_label();
+
+        let condition = self.do_interpret(&*if_node.condition)?;
+        self.generator.cg_jump_if_zero(condition, else_label)?;
+        self.generator.cg_free_register(condition);
+
+        self.do_interpret(&*if_node.then_branch)?;
+        match &if_node.else_branch {
+            Some(else_branch) => {
+                let end_label = self.generator.cg_new_label();
+                self.generator.cg_jump(end_label)?;
+                self.generator.cg_label(else_label)?;
+                self.do_interpret(&**else_branch)?;
+                self.generator.cg_label(end_label)?;
+            },
+            None => self.generator.cg_label(else_label)?,
         }
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn while_loop(&mut self, while_node: &WhileNode) -> Result<()> {
+        let start_label = self.generator.cg_new_label();
+        let end_label = self.generator.cg_new_label();
+
+        self.generator.cg_label(start_label)?;
+        let condition = self.do_interpret(&*while_node.condition)?;
+        self.generator.cg_jump_if_zero(condition, end_label)?;
+        self.generator.cg_free_register(condition);
+
+        self.loop_body(&*while_node.body, start_label, end_label)?;
+        self.generator.cg_jump(start_label)?;
+        self.generator.cg_label(end_label)?;
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn do_while_loop(&mut self, do_while_node: &DoWhileNode) -> Result<()> {
+        let start_label = self.generator.cg_new_label();
+        let continue_label = self.generator.cg_new_label();
+        let end_label = self.generator.cg_new_label();
+
+        self.generator.cg_label(start_label)?;
+        self.loop_body(&*do_while_node.body, continue_label, end_label)?;
+
+        self.generator.cg_label(continue_label)?;
+        let condition = self.do_interpret(&*do_while_node.condition)?;
+        self.generator.cg_jump_if_not_zero(condition, start_label)?;
+        self.generator.cg_free_register(condition);
+        self.generator.cg_label(end_label)?;
+        Ok(())
     }
-}
\ No newline at end of file
+
+    #[inline(never)]
+    fn for_loop(&mut self, for_node: &ForNode) -> Result<()> {
+        let start_label = self.generator.cg_new_label();
+        let continue_label = self.generator.cg_new_label();
+        let end_label = self.generator.cg_new_label();
+
+        if let Some(init) = &for_node.init {
+            self.do_interpret(&**init)?;
+        }
+
+        self.generator.cg_label(start_label)?;
+        if let Some(condition) = &for_node.condition {
+            let condition = self.do_interpret(&**condition)?;
+            self.generator.cg_jump_if_zero(condition, end_label)?;
+            self.generator.cg_free_register(condition);
+        }
+
+        self.loop_body(&*for_node.body, continue_label, end_label)?;
+
+        self.generator.cg_label(continue_label)?;
+        if let Some(step) = &for_node.step {
+            self.do_interpret(&**step)?;
+        }
+        self.generator.cg_jump(start_label)?;
+        self.generator.cg_label(end_label)?;
+        Ok(())
+    }
+
+    fn loop_body(&mut self, body: &dyn AstNode, continue_label: usize, break_label: usize) -> Result<()> {
+        self.loops.push(LoopLabels {
+            continue_label,
+            break_label,
+        });
+        let result = self.do_interpret(body);
+        self.loops.pop();
+        result.map(|_| ())
+    }
+
+    /**
+    Frame offset and type of a variable, its declaration has been generated before any use
+    */
+    fn variable(&self, symbol: SymbolId) -> Result<(i64, DataType)> {
+        let symbol = self.symbols.get(symbol);
+        match symbol.offset {
+            Some(offset) => Ok((offset, symbol.data_type)),
+            None => Err(CompilerErrorKind::InternalError(
+                format!("Variable '{}' has no stack slot", symbol.name)
+            )),
+        }
+    }
+
+    fn load_variable(&mut self, symbol: SymbolId) -> Result<usize> {
+        let (offset, data_type) = self.variable(symbol)?;
+        self.generator.cg_load_local(offset, data_type)
+    }
+
+    fn store_variable(&mut self, reg: usize, symbol: SymbolId) -> Result<usize> {
+        let (offset, data_type) = self.variable(symbol)?;
+        self.generator.cg_store_local(reg, offset, data_type)
+    }
+
+    /**
+    Lowers `++` / `--` into load / add / store, returns None for any other operation
+    */
+    fn increment(&mut self, operator: Operations, operand: &dyn AstNode) -> Result<Option<usize>> {
+        let (delta, is_postfix) = match operator {
+            Operations::OpPreIncrement => (1, false),
+            Operations::OpPreDecrement => (-1, false),
+            Operations::OpPostIncrement => (1, true),
+            Operations::OpPostDecrement => (-1, true),
+            _ => return Ok(None),
+        };
+        let Some(target) = operand.downcast_ref::<IdentifierNode>() else {
+            return Err(CompilerErrorKind::InternalError(
+                format!("Unsupported increment target {:?}", operand)
+            ));
+        };
+
+        let old_value = if is_postfix {
+            Some(self.load_variable(target.symbol)?)
+        } else {
+            None
+        };
+
+        let data_type = operand.get_type();
+        let current = self.load_variable(target.symbol)?;
+        let new_value = if data_type.is_floating() {
+            let delta = self.generator.cg_load_float(delta as f64, data_type)?;
+            self.generator.cg_fadd(current, delta, data_type)?
+        } else {
+            let delta = self.generator.cg_load(delta as i64)?;
+            self.generator.cg_add(delta, current)?
+        };
+        let new_value = self.generator.cg_wrap(new_value, data_type)?;
+        let new_value = self.store_variable(new_value, target.symbol)?;
+
+        match old_value {
+            Some(old_value) => {
+                self.generator.cg_free_register(new_value);
+                Ok(Some(old_value))
+            },
+            None => Ok(Some(new_value)),
+        }
+    }
+
+    /**
+    Evaluates `reg1 <op> reg2` on operands of `data_type`, integer results wrap around to it
+    */
+    fn binary_operation(&mut self, operator: Operations, reg1: usize, reg2: usize, data_type: DataType) -> Result<usize> {
+        let reg = match operator {
+            Operations::OpIntAdd => {
+                self.generator.cg_add(reg1, reg2)
+            },
+            Operations::OpIntMinus => {
+                self.generator.cg_sub(reg1, reg2)
+            },
+            Operations::OpIntMul => {
+                self.generator.cg_mul(reg1, reg2)
+            },
+            Operations::OpIntDiv => {
+                self.generator.cg_div(reg1, reg2)
+            },
+            Operations::OpIntMod => {
+                self.generator.cg_mod(reg1, reg2)
+            },
+            Operations::OpIntShiftLeft => {
+                self.generator.cg_shl(reg1, reg2)
+            },
+            Operations::OpIntShiftRight => {
+                self.generator.cg_shr(reg1, reg2)
+            },
+            Operations::OpIntAnd => {
+                self.generator.cg_and(reg1, reg2)
+            },
+            Operations::OpIntOr => {
+                self.generator.cg_or(reg1, reg2)
+            },
+            Operations::OpIntXor => {
+                self.generator.cg_xor(reg1, reg2)
+            },
+            Operations::OpIntEqual => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::Equal)
+            },
+            Operations::OpIntNotEqual => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::NotEqual)
+            },
+            Operations::OpIntLess => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::Less)
+            },
+            Operations::OpIntLessEqual => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::LessEqual)
+            },
+            Operations::OpIntGreater => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::Greater)
+            },
+            Operations::OpIntGreaterEqual => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::GreaterEqual)
+            },
+            Operations::OpUIntDiv => {
+                self.generator.cg_udiv(reg1, reg2)
+            },
+            Operations::OpUIntMod => {
+                self.generator.cg_umod(reg1, reg2)
+            },
+            Operations::OpUIntShiftRight => {
+                self.generator.cg_ushr(reg1, reg2)
+            },
+            Operations::OpUIntLess => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::UnsignedLess)
+            },
+            Operations::OpUIntLessEqual => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::UnsignedLessEqual)
+            },
+            Operations::OpUIntGreater => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::UnsignedGreater)
+            },
+            Operations::OpUIntGreaterEqual => {
+                self.generator.cg_compare(reg1, reg2, CompareOp::UnsignedGreaterEqual)
+            },
+            Operations::OpFloatAdd => {
+                self.generator.cg_fadd(reg1, reg2, data_type)
+            },
+            Operations::OpFloatMinus => {
+                self.generator.cg_fsub(reg1, reg2, data_type)
+            },
+            Operations::OpFloatMul => {
+                self.generator.cg_fmul(reg1, reg2, data_type)
+            },
+            Operations::OpFloatDiv => {
+                self.generator.cg_fdiv(reg1, reg2, data_type)
+            },
+            Operations::OpFloatEqual => {
+                self.generator.cg_fcompare(reg1, reg2, CompareOp::Equal, data_type)
+            },
+            Operations::OpFloatNotEqual => {
+                self.generator.cg_fcompare(reg1, reg2, CompareOp::NotEqual, data_type)
+            },
+            Operations::OpFloatLess => {
+                self.generator.cg_fcompare(reg1, reg2, CompareOp::Less, data_type)
+            },
+            Operations::OpFloatLessEqual => {
+                self.generator.cg_fcompare(reg1, reg2, CompareOp::LessEqual, data_type)
+            },
+            Operations::OpFloatGreater => {
+                self.generator.cg_fcompare(reg1, reg2, CompareOp::Greater, data_type)
+            },
+            Operations::OpFloatGreaterEqual => {
+                self.generator.cg_fcompare(reg1, reg2, CompareOp::GreaterEqual, data_type)
+            },
+            other => unimplemented!("{:?}", other),
+        }?;
+
+        if operator.is_comparison() {
+            // 0 / 1 already
+            return Ok(reg);
+        }
+        self.generator.cg_wrap(reg, data_type)
+    }
+}
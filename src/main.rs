@@ -1,4 +1,9 @@
 #![allow(dead_code)]
+#![allow(clippy::module_inception)]
+#![allow(clippy::enum_variant_names)]
+#![allow(clippy::upper_case_acronyms)]
+#![allow(clippy::wrong_self_convention)]
+#![allow(clippy::new_without_default)]
 
 extern crate downcast_rs;
 
@@ -9,51 +14,35 @@ mod parser;
 mod source_code;
 mod codegen;
 mod interpreter;
+mod symbols;
+mod semantic;
+mod preprocessor;
+mod driver;
 
-use std::fs;
-use crate::codegen::module::output::FileOutput;
-use crate::codegen::x86::x86_generator::X86Generator;
-use crate::interpreter::ast_interpreter::ASTInterpreter;
-use crate::source_code::SourceCode;
+use crate::common::errors::diagnostic::Diagnostic;
+use crate::common::errors::renderer::DiagnosticEmitter;
+use crate::common::source_map::SourceMap;
+use crate::driver::driver::{Driver, EXIT_FAILURE, EXIT_SUCCESS, EXIT_USAGE, STACK_SIZE};
+use crate::driver::options::{Options, USAGE};
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    dbg!(&args);
-
-
-    match fs::read_to_string(&args[1]) {
-        Ok(text) => {
-            let source_code = SourceCode::new(text);
-            let mut scanner = source_code.into_scanner();
-            let mut parser = source_code.into_parser(&mut scanner);
-
-
-            let mut file = FileOutput::new("./output/output.txt").unwrap();
-            let code_generator = Box::new(X86Generator::new(&mut file));
-            let mut interpreter = ASTInterpreter::new(code_generator);
-
-            match parser.parse() {
-                Ok(ast) => {
-                    println!("{:#?}", ast);
-                    interpreter.interpret(&*ast).expect("Failed to interpret");
-                },
-                Err(e) => panic!("{:?}", e),
-            }
-
-
-            // loop {
-            //     match scanner.scan() {
-            //         Ok(token) => {
-            //             println!("{:?}", token);
-            //             if token.get_token_type() == TokenType::Eof {
-            //                 break;
-            //             }
-            //         },
-            //         Err(e) => panic!("{:?}", e),
-            //     }
-            // }
-        }
-        Err(e) => panic!("{:?}", e),
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            DiagnosticEmitter::new().emit(&Diagnostic::from(e), &SourceMap::new());
+            eprintln!("Try 'c-compiler --help' for more information.");
+            std::process::exit(EXIT_USAGE);
+        },
     };
-
+    if options.help {
+        print!("{}", USAGE);
+        std::process::exit(EXIT_SUCCESS);
+    }
+    let compiler = std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || Driver::new(options).run())
+        .expect("Cannot start the compiler thread");
+    // a panic has been reported already
+    std::process::exit(compiler.join().unwrap_or(EXIT_FAILURE));
 }
@@ -0,0 +1,65 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ <p> Assignment expression `target = value`. </p>
+
+ For compound assignments such as `target += value` the op holds the arithmetic
+ operation, which is lowered into load / op / store.
+ */
+pub struct AssignmentNode {
+
+    core: AstNodeCore,
+
+    pub target: Box<dyn AstNode>,
+    pub value: Box<dyn AstNode>,
+
+}
+
+impl AssignmentNode {
+    pub fn new(target: Box<dyn AstNode>, value: Box<dyn AstNode>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            target,
+            value,
+        }
+    }
+}
+
+impl AstNode for AssignmentNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, op: Operations) {
+        self.core.op = op;
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
+}
+
+impl Debug for AssignmentNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.get_op() {
+            Operations::OpAssign => write!(f, "[ {:?} = {:?} ]", self.target, self.value),
+            op => write!(f, "[ {:?} {:?}= {:?} ]", self.target, op, self.value),
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use std::fmt::{Debug, Formatter};
 use crate::parser::ast::core::AstNodeCore;
 use crate::parser::ast::core::AstNode;
+use crate::parser::data_type::DataType;
 use crate::parser::operations::Operations;
+use crate::common::span::Span;
 
 pub struct BinaryAstNode {
 
@@ -30,6 +32,22 @@ impl AstNode for BinaryAstNode {
     fn set_op(&mut self, op: Operations) {
         self.core.op = op;
     }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
 }
 
 impl Debug for BinaryAstNode {
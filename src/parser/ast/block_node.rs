@@ -1,6 +1,7 @@
 use std::fmt::{Debug, Formatter};
 use crate::parser::ast::core::{AstNode, AstNodeCore};
 use crate::parser::operations::Operations;
+use crate::common::span::Span;
 
 pub struct BlockNode {
 
@@ -12,11 +13,11 @@ pub struct BlockNode {
 
 impl Debug for BlockNode {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Statements: {{\n")?;
+        writeln!(f, "Statements: {{")?;
         for ast_node in self.block.iter() {
-            write!(f, "{:?}\n", ast_node)?;
+            writeln!(f, "{:?}", ast_node)?;
         }
-        write!(f, "}}\n")?;
+        writeln!(f, "}}")?;
         Ok(())
     }
 }
@@ -29,6 +30,14 @@ impl AstNode for BlockNode {
     fn set_op(&mut self, _op: Operations) {
         unimplemented!()
     }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
 }
 
 impl BlockNode {
@@ -0,0 +1,66 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::symbols::symbol_table::SymbolId;
+use crate::common::span::Span;
+
+pub struct CallNode {
+
+    core: AstNodeCore,
+
+    pub name: String,
+    pub symbol: SymbolId,
+    pub args: Vec<Box<dyn AstNode>>,
+
+}
+
+impl CallNode {
+    pub fn new(name: String, symbol: SymbolId, args: Vec<Box<dyn AstNode>>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            name,
+            symbol,
+            args,
+        }
+    }
+}
+
+impl AstNode for CallNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, op: Operations) {
+        self.core.op = op;
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
+}
+
+impl Debug for CallNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{:?}", arg)?;
+        }
+        write!(f, ")")
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ <p> Conversion of the operand to the type of the node. </p>
+
+ Inserted by the semantic analysis wherever C converts a value implicitly.
+ */
+pub struct CastNode {
+
+    core: AstNodeCore,
+
+    pub operand: Box<dyn AstNode>,
+
+}
+
+impl CastNode {
+    pub fn new(operand: Box<dyn AstNode>, data_type: DataType) -> Self {
+        let mut core = AstNodeCore::new();
+        core.op = Operations::OpCast;
+        core.data_type = data_type;
+        Self {
+            core,
+            operand,
+        }
+    }
+}
+
+impl AstNode for CastNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
+}
+
+impl Debug for CastNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({:?}){:?}", self.core.data_type, self.operand)
+    }
+}
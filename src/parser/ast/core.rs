@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 use downcast_rs::{impl_downcast, Downcast};
+use crate::common::span::Span;
+use crate::parser::data_type::DataType;
 use crate::parser::operations::Operations;
 
 
@@ -7,6 +9,31 @@ pub trait AstNode: Debug + Downcast {
     fn get_op(&self) -> Operations;
 
     fn set_op(&mut self, op: Operations);
+
+    /**
+    Source the node was parsed from, from its first token to its last
+    */
+    fn get_span(&self) -> Span;
+
+    fn set_span(&mut self, span: Span);
+
+    /**
+    Whether the node designates an object that can be assigned to
+    */
+    fn is_lvalue(&self) -> bool {
+        false
+    }
+
+    /**
+    C type of the value of an expression, annotated by the semantic analysis. Statements are void
+    */
+    fn get_type(&self) -> DataType {
+        DataType::Void
+    }
+
+    fn set_type(&mut self, _data_type: DataType) {
+        unimplemented!()
+    }
 }
 impl_downcast!(AstNode);
 
@@ -14,11 +41,13 @@ impl_downcast!(AstNode);
 pub struct AstNodeCore {
 
     pub op: Operations,
+    pub data_type: DataType,
+    pub span: Span,
 
 }
 
 impl AstNodeCore {
     pub fn new() -> Self {
-        Self { op: Operations::OpNone }
+        Self { op: Operations::OpNone, data_type: DataType::Void, span: Span::default() }
     }
 }
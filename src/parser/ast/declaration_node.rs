@@ -0,0 +1,85 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::symbols::symbol_table::SymbolId;
+use crate::common::span::Span;
+
+pub struct Declarator {
+
+    pub name: String,
+    pub symbol: SymbolId,
+    /**
+    Type of the declared variable, the base type unless the declarator makes it a pointer
+    */
+    pub data_type: DataType,
+    pub init: Option<Box<dyn AstNode>>,
+
+}
+
+impl Declarator {
+    pub fn new(name: String, symbol: SymbolId, data_type: DataType, init: Option<Box<dyn AstNode>>) -> Self {
+        Self { name, symbol, data_type, init }
+    }
+}
+
+/**
+ <p> A declaration statement, e.g. `int a, b = 1 + 2;` </p>
+
+ All declarators share the same base type.
+ */
+pub struct DeclarationNode {
+
+    core: AstNodeCore,
+
+    pub data_type: DataType,
+    pub declarators: Vec<Declarator>,
+
+}
+
+impl DeclarationNode {
+    pub fn new(data_type: DataType, declarators: Vec<Declarator>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            data_type,
+            declarators,
+        }
+    }
+}
+
+impl AstNode for DeclarationNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for DeclarationNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Declare {:?}", self.data_type)?;
+        for (i, declarator) in self.declarators.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            if declarator.data_type != self.data_type {
+                write!(f, " *")?;
+            }
+            write!(f, " {}", declarator.name)?;
+            if let Some(init) = &declarator.init {
+                write!(f, " = {:?}", init)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+pub struct DoWhileNode {
+
+    core: AstNodeCore,
+
+    pub body: Box<dyn AstNode>,
+    pub condition: Box<dyn AstNode>,
+
+}
+
+impl DoWhileNode {
+    pub fn new(body: Box<dyn AstNode>, condition: Box<dyn AstNode>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            body,
+            condition,
+        }
+    }
+}
+
+impl AstNode for DoWhileNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for DoWhileNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Do {:?} While ({:?})", self.body, self.condition)
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ An expression evaluated only for its side effects, its value is discarded.
+ */
+pub struct ExpressionStatementNode {
+
+    core: AstNodeCore,
+
+    pub expr: Box<dyn AstNode>,
+
+}
+
+impl ExpressionStatementNode {
+    pub fn new(expr: Box<dyn AstNode>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            expr,
+        }
+    }
+}
+
+impl AstNode for ExpressionStatementNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, op: Operations) {
+        self.core.op = op;
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for ExpressionStatementNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?};", self.expr)
+    }
+}
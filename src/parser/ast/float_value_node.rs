@@ -0,0 +1,62 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ Floating constant, typed double or float (`f` suffix) by the literal itself
+ */
+pub struct FloatValueNode {
+
+    core: AstNodeCore,
+
+    value: f64,
+}
+
+impl FloatValueNode {
+    pub fn new(value: f64, data_type: DataType) -> Self {
+        let mut core = AstNodeCore::new();
+        core.data_type = data_type;
+        Self {
+            core,
+            value,
+        }
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl AstNode for FloatValueNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        todo!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
+}
+
+impl Debug for FloatValueNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, " value: {:?} ", self.value)
+    }
+}
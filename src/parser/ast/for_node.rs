@@ -0,0 +1,61 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ <p> `for (init; condition; step) body` </p>
+
+ Every clause is optional, a missing condition loops forever.
+ */
+pub struct ForNode {
+
+    core: AstNodeCore,
+
+    pub init: Option<Box<dyn AstNode>>,
+    pub condition: Option<Box<dyn AstNode>>,
+    pub step: Option<Box<dyn AstNode>>,
+    pub body: Box<dyn AstNode>,
+
+}
+
+impl ForNode {
+    pub fn new(
+        init: Option<Box<dyn AstNode>>,
+        condition: Option<Box<dyn AstNode>>,
+        step: Option<Box<dyn AstNode>>,
+        body: Box<dyn AstNode>,
+    ) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            init,
+            condition,
+            step,
+            body,
+        }
+    }
+}
+
+impl AstNode for ForNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for ForNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "For ({:?}; {:?}; {:?}) {:?}", self.init, self.condition, self.step, self.body)
+    }
+}
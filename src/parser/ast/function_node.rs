@@ -0,0 +1,89 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::symbols::symbol_table::SymbolId;
+use crate::common::span::Span;
+
+pub struct Parameter {
+
+    pub name: String,
+    pub symbol: SymbolId,
+    pub data_type: DataType,
+
+}
+
+impl Parameter {
+    pub fn new(name: String, symbol: SymbolId, data_type: DataType) -> Self {
+        Self { name, symbol, data_type }
+    }
+}
+
+/**
+ <p> A function definition, or a prototype when there is no body. </p>
+ */
+pub struct FunctionNode {
+
+    core: AstNodeCore,
+
+    pub name: String,
+    pub symbol: SymbolId,
+    pub return_type: DataType,
+    pub params: Vec<Parameter>,
+    pub body: Option<Box<dyn AstNode>>,
+
+}
+
+impl FunctionNode {
+    pub fn new(
+        name: String,
+        symbol: SymbolId,
+        return_type: DataType,
+        params: Vec<Parameter>,
+        body: Option<Box<dyn AstNode>>,
+    ) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            name,
+            symbol,
+            return_type,
+            params,
+            body,
+        }
+    }
+}
+
+impl AstNode for FunctionNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for FunctionNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Function {:?} {}(", self.return_type, self.name)?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?} {}", param.data_type, param.name)?;
+        }
+        write!(f, ")")?;
+        match &self.body {
+            Some(body) => write!(f, " {:?}", body),
+            None => write!(f, ";"),
+        }
+    }
+}
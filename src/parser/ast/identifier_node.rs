@@ -0,0 +1,61 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::symbols::symbol_table::SymbolId;
+use crate::common::span::Span;
+
+pub struct IdentifierNode {
+
+    core: AstNodeCore,
+
+    pub name: String,
+    pub symbol: SymbolId,
+
+}
+
+impl IdentifierNode {
+    pub fn new(name: String, symbol: SymbolId) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            name,
+            symbol,
+        }
+    }
+}
+
+impl AstNode for IdentifierNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, op: Operations) {
+        self.core.op = op;
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn is_lvalue(&self) -> bool {
+        true
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
+}
+
+impl Debug for IdentifierNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, " {} ", self.name)
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+pub struct IfNode {
+
+    core: AstNodeCore,
+
+    pub condition: Box<dyn AstNode>,
+    pub then_branch: Box<dyn AstNode>,
+    pub else_branch: Option<Box<dyn AstNode>>,
+
+}
+
+impl IfNode {
+    pub fn new(
+        condition: Box<dyn AstNode>,
+        then_branch: Box<dyn AstNode>,
+        else_branch: Option<Box<dyn AstNode>>,
+    ) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            condition,
+            then_branch,
+            else_branch,
+        }
+    }
+}
+
+impl AstNode for IfNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for IfNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "If ({:?}) {:?}", self.condition, self.then_branch)?;
+        if let Some(else_branch) = &self.else_branch {
+            write!(f, " Else {:?}", else_branch)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ <p> Short-circuit `&&` / `||` expression. </p>
+
+ Unlike `BinaryAstNode`, the right hand side is only evaluated when the left hand
+ side does not already decide the result.
+ */
+pub struct LogicalAstNode {
+
+    core: AstNodeCore,
+
+    pub left: Box<dyn AstNode>,
+    pub right: Box<dyn AstNode>,
+
+}
+
+impl LogicalAstNode {
+    pub fn new(left: Box<dyn AstNode>, right: Box<dyn AstNode>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            left,
+            right,
+        }
+    }
+}
+
+impl AstNode for LogicalAstNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, op: Operations) {
+        self.core.op = op;
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
+}
+
+impl Debug for LogicalAstNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[ {:?} {:?} {:?} ]", self.left, self.get_op(), self.right)
+    }
+}
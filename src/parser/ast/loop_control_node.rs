@@ -0,0 +1,45 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ `break` or `continue`, always targeting the innermost enclosing loop
+ */
+pub struct LoopControlNode {
+
+    core: AstNodeCore,
+
+}
+
+impl LoopControlNode {
+    pub fn new() -> Self {
+        Self {
+            core: AstNodeCore::new(),
+        }
+    }
+}
+
+impl AstNode for LoopControlNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, op: Operations) {
+        self.core.op = op;
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for LoopControlNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.get_op())
+    }
+}
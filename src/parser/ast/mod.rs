@@ -2,4 +2,22 @@ pub mod core;
 pub mod binary_op_node;
 pub mod value_node;
 pub mod print_node;
-pub mod block_node;
\ No newline at end of file
+pub mod block_node;
+pub mod identifier_node;
+pub mod declaration_node;
+pub mod expression_statement_node;
+pub mod assignment_node;
+pub mod logical_op_node;
+pub mod unary_op_node;
+pub mod if_node;
+pub mod while_node;
+pub mod do_while_node;
+pub mod for_node;
+pub mod loop_control_node;
+pub mod function_node;
+pub mod call_node;
+pub mod return_node;
+pub mod program_node;
+pub mod cast_node;
+pub mod float_value_node;
+pub mod string_node;
@@ -1,6 +1,7 @@
 use std::fmt::{Debug, Formatter};
 use crate::parser::ast::core::{AstNode, AstNodeCore};
 use crate::parser::operations::Operations;
+use crate::common::span::Span;
 
 pub struct PrintAstNode {
 
@@ -36,4 +37,12 @@ impl AstNode for PrintAstNode {
         self.core.op = op;
     }
 
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,51 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ The translation unit: every top level declaration of the source file, in order
+ */
+pub struct ProgramNode {
+
+    core: AstNodeCore,
+
+    pub declarations: Vec<Box<dyn AstNode>>,
+
+}
+
+impl ProgramNode {
+    pub fn new(declarations: Vec<Box<dyn AstNode>>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            declarations,
+        }
+    }
+}
+
+impl AstNode for ProgramNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for ProgramNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for declaration in self.declarations.iter() {
+            writeln!(f, "{:?}", declaration)?;
+        }
+        Ok(())
+    }
+}
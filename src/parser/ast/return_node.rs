@@ -0,0 +1,48 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+pub struct ReturnNode {
+
+    core: AstNodeCore,
+
+    pub value: Option<Box<dyn AstNode>>,
+
+}
+
+impl ReturnNode {
+    pub fn new(value: Option<Box<dyn AstNode>>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            value,
+        }
+    }
+}
+
+impl AstNode for ReturnNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for ReturnNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "Return {:?}", value),
+            None => write!(f, "Return"),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+/**
+ <p> String literal, adjacent literals are already concatenated. </p>
+
+ Its value is the address of the bytes, which are stored with a terminating NUL.
+ */
+pub struct StringNode {
+
+    core: AstNodeCore,
+
+    value: Vec<u8>,
+}
+
+impl StringNode {
+    pub fn new(value: Vec<u8>) -> Self {
+        let mut core = AstNodeCore::new();
+        core.data_type = DataType::CharPointer;
+        Self {
+            core,
+            value,
+        }
+    }
+
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl AstNode for StringNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        todo!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
+}
+
+impl Debug for StringNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, " string: \"{}\" ", self.value.escape_ascii())
+    }
+}
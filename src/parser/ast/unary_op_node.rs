@@ -0,0 +1,54 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+pub struct UnaryAstNode {
+
+    core: AstNodeCore,
+
+    pub operand: Box<dyn AstNode>,
+
+}
+
+impl UnaryAstNode {
+    pub fn new(operand: Box<dyn AstNode>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            operand,
+        }
+    }
+}
+
+impl AstNode for UnaryAstNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, op: Operations) {
+        self.core.op = op;
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
+}
+
+impl Debug for UnaryAstNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[ {:?}{:?} ]", self.get_op(), self.operand)
+    }
+}
@@ -1,19 +1,26 @@
 use std::fmt::{Debug, Formatter};
 use crate::codegen::core::RegLoadable;
 use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::data_type::DataType;
 use crate::parser::operations::Operations;
+use crate::common::span::Span;
 
 pub struct ValueNode {
 
     core: AstNodeCore,
 
+    /**
+    Bit pattern of the constant, unsigned values above i64::MAX wrap around
+    */
     value: i64,
 }
 
 impl ValueNode {
-    pub fn new(value: i64) -> Self {
+    pub fn new(value: i64, data_type: DataType) -> Self {
+        let mut core = AstNodeCore::new();
+        core.data_type = data_type;
         Self {
-            core: AstNodeCore::new(),
+            core,
             value,
         }
     }
@@ -41,6 +48,22 @@ impl AstNode for ValueNode {
     fn set_op(&mut self, _op: Operations) {
         todo!()
     }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+
+    fn get_type(&self) -> DataType {
+        self.core.data_type
+    }
+
+    fn set_type(&mut self, data_type: DataType) {
+        self.core.data_type = data_type;
+    }
 }
 
 impl Debug for ValueNode {
@@ -0,0 +1,47 @@
+use std::fmt::{Debug, Formatter};
+use crate::parser::ast::core::{AstNode, AstNodeCore};
+use crate::parser::operations::Operations;
+use crate::common::span::Span;
+
+pub struct WhileNode {
+
+    core: AstNodeCore,
+
+    pub condition: Box<dyn AstNode>,
+    pub body: Box<dyn AstNode>,
+
+}
+
+impl WhileNode {
+    pub fn new(condition: Box<dyn AstNode>, body: Box<dyn AstNode>) -> Self {
+        Self {
+            core: AstNodeCore::new(),
+            condition,
+            body,
+        }
+    }
+}
+
+impl AstNode for WhileNode {
+    fn get_op(&self) -> Operations {
+        self.core.op
+    }
+
+    fn set_op(&mut self, _op: Operations) {
+        unimplemented!()
+    }
+
+    fn get_span(&self) -> Span {
+        self.core.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.core.span = span;
+    }
+}
+
+impl Debug for WhileNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "While ({:?}) {:?}", self.condition, self.body)
+    }
+}
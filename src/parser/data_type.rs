@@ -0,0 +1,262 @@
+use std::fmt::{Debug, Formatter};
+use crate::scanner::tokens::TokenType;
+
+/**
+ <p> C types, laid out as on x86-64. </p>
+
+ `char` is signed, `signed char` is the same type here. The only pointer type is `char *`,
+ the type of string literals.
+ */
+#[derive(Clone, Copy, PartialEq)]
+pub enum DataType {
+
+    Void,
+    Char,
+    UnsignedChar,
+    Short,
+    UnsignedShort,
+    Int,
+    UnsignedInt,
+    Long,
+    UnsignedLong,
+    LongLong,
+    UnsignedLongLong,
+    Float,
+    Double,
+    CharPointer,
+
+}
+
+impl DataType {
+
+    pub fn is_type_specifier(token_type: TokenType) -> bool {
+        matches!(
+            token_type,
+            TokenType::KeyVoid | TokenType::KeyChar | TokenType::KeyShort | TokenType::KeyInt
+            | TokenType::KeyLong | TokenType::KeySigned | TokenType::KeyUnsigned
+            | TokenType::KeyFloat | TokenType::KeyDouble
+        )
+    }
+
+    /**
+    The type named by a list of type specifiers in any order, e.g. `unsigned long int`,
+    None if they do not form a valid type.
+    */
+    pub fn from_specifiers(specifiers: &[TokenType]) -> Option<DataType> {
+        let count = |token_type: TokenType| specifiers.iter().filter(|t| **t == token_type).count();
+        let (void, char, short, int, long) = (
+            count(TokenType::KeyVoid),
+            count(TokenType::KeyChar),
+            count(TokenType::KeyShort),
+            count(TokenType::KeyInt),
+            count(TokenType::KeyLong),
+        );
+        let (signed, unsigned) = (count(TokenType::KeySigned), count(TokenType::KeyUnsigned));
+
+        if signed + unsigned > 1 || void + char + short > 1 || int > 1 || long > 2 {
+            return None;
+        }
+        if void == 1 {
+            return (specifiers.len() == 1).then_some(DataType::Void);
+        }
+        // `long double` is not supported
+        if specifiers.len() == 1 && specifiers[0] == TokenType::KeyFloat {
+            return Some(DataType::Float);
+        }
+        if specifiers.len() == 1 && specifiers[0] == TokenType::KeyDouble {
+            return Some(DataType::Double);
+        }
+        if count(TokenType::KeyFloat) + count(TokenType::KeyDouble) > 0 {
+            return None;
+        }
+
+        let data_type = match (char, short, long) {
+            (1, 0, 0) if int == 0 => DataType::Char,
+            (0, 1, 0) => DataType::Short,
+            (0, 0, 0) => DataType::Int,
+            (0, 0, 1) => DataType::Long,
+            (0, 0, 2) => DataType::LongLong,
+            _ => return None,
+        };
+        Some(if unsigned == 1 { data_type.to_unsigned() } else { data_type })
+    }
+
+    /**
+    Size of the type in bytes, as laid out on x86-64.
+    */
+    pub fn size(&self) -> usize {
+        match self {
+            DataType::Void => 0,
+            DataType::Char | DataType::UnsignedChar => 1,
+            DataType::Short | DataType::UnsignedShort => 2,
+            DataType::Int | DataType::UnsignedInt | DataType::Float => 4,
+            DataType::Long | DataType::UnsignedLong
+            | DataType::LongLong | DataType::UnsignedLongLong | DataType::Double
+            | DataType::CharPointer => 8,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, DataType::Void | DataType::Float | DataType::Double | DataType::CharPointer)
+    }
+
+    /**
+    Integer and floating types, the operands of arithmetic operators
+    */
+    pub fn is_arithmetic(&self) -> bool {
+        self.is_integer() || self.is_floating()
+    }
+
+    pub fn is_floating(&self) -> bool {
+        matches!(self, DataType::Float | DataType::Double)
+    }
+
+    /**
+    Whether the type is a signed integer type, floating types are neither signed nor unsigned
+    */
+    pub fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            DataType::Char | DataType::Short | DataType::Int | DataType::Long | DataType::LongLong
+        )
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        self.is_integer() && !self.is_signed()
+    }
+
+    /**
+    Largest value of an integer type
+    */
+    pub fn max_value(&self) -> u64 {
+        let bits = 8 * self.size() as u32;
+        if self.is_signed() {
+            (1 << (bits - 1)) - 1
+        } else {
+            u64::MAX >> (64 - bits)
+        }
+    }
+
+    pub fn to_unsigned(&self) -> DataType {
+        match self {
+            DataType::Char => DataType::UnsignedChar,
+            DataType::Short => DataType::UnsignedShort,
+            DataType::Int => DataType::UnsignedInt,
+            DataType::Long => DataType::UnsignedLong,
+            DataType::LongLong => DataType::UnsignedLongLong,
+            other => *other,
+        }
+    }
+
+    /**
+    Integer conversion rank, signed and unsigned variants of a type rank the same
+    */
+    fn rank(&self) -> usize {
+        match self {
+            DataType::Void | DataType::Float | DataType::Double | DataType::CharPointer => 0,
+            DataType::Char | DataType::UnsignedChar => 1,
+            DataType::Short | DataType::UnsignedShort => 2,
+            DataType::Int | DataType::UnsignedInt => 3,
+            DataType::Long | DataType::UnsignedLong => 4,
+            DataType::LongLong | DataType::UnsignedLongLong => 5,
+        }
+    }
+
+    /**
+    Integer promotion, applied to the operands of arithmetic operators: types ranked below
+    int become int, which holds all of their values
+    */
+    pub fn promote(&self) -> DataType {
+        if self.is_integer() && self.rank() < DataType::Int.rank() {
+            DataType::Int
+        } else {
+            *self
+        }
+    }
+
+    /**
+    Usual arithmetic conversions: the type both operands of a binary operator are converted to
+    */
+    pub fn common_type(left: DataType, right: DataType) -> DataType {
+        // any floating operand makes the operation floating, in the wider of the floating types
+        if left == DataType::Double || right == DataType::Double {
+            return DataType::Double;
+        }
+        if left == DataType::Float || right == DataType::Float {
+            return DataType::Float;
+        }
+
+        let (left, right) = (left.promote(), right.promote());
+        if left == right {
+            return left;
+        }
+        if left.is_signed() == right.is_signed() {
+            return if left.rank() >= right.rank() { left } else { right };
+        }
+
+        let (signed, unsigned) = if left.is_signed() { (left, right) } else { (right, left) };
+        if unsigned.rank() >= signed.rank() {
+            unsigned
+        } else if signed.size() > unsigned.size() {
+            // the signed type holds every value of the unsigned one
+            signed
+        } else {
+            signed.to_unsigned()
+        }
+    }
+
+}
+
+impl Debug for DataType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DataType::Void => "void",
+            DataType::Char => "char",
+            DataType::UnsignedChar => "unsigned char",
+            DataType::Short => "short",
+            DataType::UnsignedShort => "unsigned short",
+            DataType::Int => "int",
+            DataType::UnsignedInt => "unsigned int",
+            DataType::Long => "long",
+            DataType::UnsignedLong => "unsigned long",
+            DataType::LongLong => "long long",
+            DataType::UnsignedLongLong => "unsigned long long",
+            DataType::Float => "float",
+            DataType::Double => "double",
+            DataType::CharPointer => "char *",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_specifiers() {
+        use TokenType::{KeyChar, KeyDouble, KeyInt, KeyLong, KeyShort, KeySigned, KeyUnsigned, KeyVoid};
+        assert_eq!(DataType::from_specifiers(&[KeyUnsigned]), Some(DataType::UnsignedInt));
+        assert_eq!(DataType::from_specifiers(&[KeyLong, KeyUnsigned, KeyInt, KeyLong]), Some(DataType::UnsignedLongLong));
+        assert_eq!(DataType::from_specifiers(&[KeySigned, KeyChar]), Some(DataType::Char));
+        assert_eq!(DataType::from_specifiers(&[KeyShort, KeyInt]), Some(DataType::Short));
+        assert_eq!(DataType::from_specifiers(&[KeyChar, KeyInt]), None);
+        assert_eq!(DataType::from_specifiers(&[KeyLong, KeyLong, KeyLong]), None);
+        assert_eq!(DataType::from_specifiers(&[KeySigned, KeyUnsigned]), None);
+        assert_eq!(DataType::from_specifiers(&[KeyVoid, KeyInt]), None);
+        assert_eq!(DataType::from_specifiers(&[KeyDouble]), Some(DataType::Double));
+        assert_eq!(DataType::from_specifiers(&[KeyLong, KeyDouble]), None);
+    }
+
+    #[test]
+    fn test_usual_arithmetic_conversions() {
+        use DataType::*;
+        assert_eq!(DataType::common_type(Char, UnsignedShort), Int);
+        assert_eq!(DataType::common_type(Int, UnsignedInt), UnsignedInt);
+        assert_eq!(DataType::common_type(UnsignedInt, Long), Long);
+        assert_eq!(DataType::common_type(Long, UnsignedLongLong), UnsignedLongLong);
+        assert_eq!(DataType::common_type(LongLong, UnsignedLong), UnsignedLongLong);
+        assert_eq!(DataType::common_type(UnsignedLongLong, Float), Float);
+        assert_eq!(DataType::common_type(Float, Double), Double);
+    }
+}
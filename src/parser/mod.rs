@@ -2,4 +2,5 @@ pub mod parser;
 pub mod ast;
 pub mod operations;
 pub mod precedence;
-pub mod parse_func;
\ No newline at end of file
+pub mod parse_func;
+pub mod data_type;
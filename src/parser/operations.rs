@@ -9,6 +9,59 @@ pub enum Operations {
     OpIntMinus,
     OpIntMul,
     OpIntDiv,
+    OpIntMod,
+    OpIntShiftLeft,
+    OpIntShiftRight,
+    OpIntAnd,
+    OpIntOr,
+    OpIntXor,
+
+    OpIntEqual,
+    OpIntNotEqual,
+    OpIntLess,
+    OpIntLessEqual,
+    OpIntGreater,
+    OpIntGreaterEqual,
+
+    // unsigned variants, chosen by the semantic analysis from the operand types
+    OpUIntDiv,
+    OpUIntMod,
+    OpUIntShiftRight,
+    OpUIntLess,
+    OpUIntLessEqual,
+    OpUIntGreater,
+    OpUIntGreaterEqual,
+
+    // floating point variants, chosen the same way
+    OpFloatAdd,
+    OpFloatMinus,
+    OpFloatMul,
+    OpFloatDiv,
+    OpFloatEqual,
+    OpFloatNotEqual,
+    OpFloatLess,
+    OpFloatLessEqual,
+    OpFloatGreater,
+    OpFloatGreaterEqual,
+    OpFloatNegate,
+
+    OpLogicalAnd,
+    OpLogicalOr,
+    OpLogicalNot,
+
+    OpUnaryPlus,
+    OpNegate,
+    OpBitNot,
+    OpPreIncrement,
+    OpPreDecrement,
+    OpPostIncrement,
+    OpPostDecrement,
+
+    OpAssign,
+    OpCast,
+
+    OpBreak,
+    OpContinue,
 
     OpValueInt,
 
@@ -22,7 +75,115 @@ impl Debug for Operations {
             Operations::OpIntMinus => write!(f, "-"),
             Operations::OpIntMul => write!(f, "*"),
             Operations::OpIntDiv => write!(f, "/"),
+            Operations::OpIntMod => write!(f, "%"),
+            Operations::OpIntShiftLeft => write!(f, "<<"),
+            Operations::OpIntShiftRight => write!(f, ">>"),
+            Operations::OpIntAnd => write!(f, "&"),
+            Operations::OpIntOr => write!(f, "|"),
+            Operations::OpIntXor => write!(f, "^"),
+            Operations::OpIntEqual => write!(f, "=="),
+            Operations::OpIntNotEqual => write!(f, "!="),
+            Operations::OpIntLess => write!(f, "<"),
+            Operations::OpIntLessEqual => write!(f, "<="),
+            Operations::OpIntGreater => write!(f, ">"),
+            Operations::OpIntGreaterEqual => write!(f, ">="),
+            Operations::OpUIntDiv => write!(f, "/u"),
+            Operations::OpUIntMod => write!(f, "%u"),
+            Operations::OpUIntShiftRight => write!(f, ">>u"),
+            Operations::OpUIntLess => write!(f, "<u"),
+            Operations::OpUIntLessEqual => write!(f, "<=u"),
+            Operations::OpUIntGreater => write!(f, ">u"),
+            Operations::OpUIntGreaterEqual => write!(f, ">=u"),
+            Operations::OpFloatAdd => write!(f, "+f"),
+            Operations::OpFloatMinus => write!(f, "-f"),
+            Operations::OpFloatMul => write!(f, "*f"),
+            Operations::OpFloatDiv => write!(f, "/f"),
+            Operations::OpFloatEqual => write!(f, "==f"),
+            Operations::OpFloatNotEqual => write!(f, "!=f"),
+            Operations::OpFloatLess => write!(f, "<f"),
+            Operations::OpFloatLessEqual => write!(f, "<=f"),
+            Operations::OpFloatGreater => write!(f, ">f"),
+            Operations::OpFloatGreaterEqual => write!(f, ">=f"),
+            Operations::OpFloatNegate => write!(f, "-f"),
+            Operations::OpLogicalAnd => write!(f, "&&"),
+            Operations::OpLogicalOr => write!(f, "||"),
+            Operations::OpLogicalNot => write!(f, "!"),
+            Operations::OpUnaryPlus => write!(f, "+"),
+            Operations::OpNegate => write!(f, "-"),
+            Operations::OpBitNot => write!(f, "~"),
+            Operations::OpPreIncrement => write!(f, "++"),
+            Operations::OpPreDecrement => write!(f, "--"),
+            Operations::OpPostIncrement => write!(f, "(post)++"),
+            Operations::OpPostDecrement => write!(f, "(post)--"),
+            Operations::OpAssign => write!(f, "="),
+            Operations::OpCast => write!(f, "Cast"),
+            Operations::OpBreak => write!(f, "Break"),
+            Operations::OpContinue => write!(f, "Continue"),
             _ => unimplemented!(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl Operations {
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            Operations::OpIntEqual | Operations::OpIntNotEqual
+            | Operations::OpIntLess | Operations::OpIntLessEqual
+            | Operations::OpIntGreater | Operations::OpIntGreaterEqual
+            | Operations::OpUIntLess | Operations::OpUIntLessEqual
+            | Operations::OpUIntGreater | Operations::OpUIntGreaterEqual
+            | Operations::OpFloatEqual | Operations::OpFloatNotEqual
+            | Operations::OpFloatLess | Operations::OpFloatLessEqual
+            | Operations::OpFloatGreater | Operations::OpFloatGreaterEqual
+        )
+    }
+
+    /**
+    Whether the operation is only defined on integer operands
+    */
+    pub fn is_integer_only(&self) -> bool {
+        matches!(
+            self,
+            Operations::OpIntMod | Operations::OpIntShiftLeft | Operations::OpIntShiftRight
+            | Operations::OpIntAnd | Operations::OpIntOr | Operations::OpIntXor
+            | Operations::OpBitNot
+        )
+    }
+
+    /**
+    The variant of an operation on unsigned operands, if it differs from the signed one
+    */
+    pub fn to_unsigned(self) -> Operations {
+        match self {
+            Operations::OpIntDiv => Operations::OpUIntDiv,
+            Operations::OpIntMod => Operations::OpUIntMod,
+            Operations::OpIntShiftRight => Operations::OpUIntShiftRight,
+            Operations::OpIntLess => Operations::OpUIntLess,
+            Operations::OpIntLessEqual => Operations::OpUIntLessEqual,
+            Operations::OpIntGreater => Operations::OpUIntGreater,
+            Operations::OpIntGreaterEqual => Operations::OpUIntGreaterEqual,
+            other => other,
+        }
+    }
+
+    /**
+    The variant of an operation on floating point operands, if it differs from the integer one
+    */
+    pub fn to_floating(self) -> Operations {
+        match self {
+            Operations::OpIntAdd => Operations::OpFloatAdd,
+            Operations::OpIntMinus => Operations::OpFloatMinus,
+            Operations::OpIntMul => Operations::OpFloatMul,
+            Operations::OpIntDiv => Operations::OpFloatDiv,
+            Operations::OpIntEqual => Operations::OpFloatEqual,
+            Operations::OpIntNotEqual => Operations::OpFloatNotEqual,
+            Operations::OpIntLess => Operations::OpFloatLess,
+            Operations::OpIntLessEqual => Operations::OpFloatLessEqual,
+            Operations::OpIntGreater => Operations::OpFloatGreater,
+            Operations::OpIntGreaterEqual => Operations::OpFloatGreaterEqual,
+            Operations::OpNegate => Operations::OpFloatNegate,
+            other => other,
+        }
+    }
+}
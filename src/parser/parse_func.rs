@@ -1,20 +1,28 @@
+use crate::parser::ast::assignment_node::AssignmentNode;
 use crate::parser::ast::binary_op_node::BinaryAstNode;
+use crate::parser::ast::call_node::CallNode;
 use crate::parser::ast::core::AstNode;
 use crate::parser::operations::Operations;
 use crate::parser::parser::Parser;
 use crate::scanner::tokens::{TokenType};
 use crate::common::Result;
+use crate::common::errors::error::CompilerErrorKind::CompilerError;
+use crate::parser::ast::identifier_node::IdentifierNode;
+use crate::parser::ast::logical_op_node::LogicalAstNode;
+use crate::parser::ast::unary_op_node::UnaryAstNode;
 use crate::parser::ast::value_node::ValueNode;
+use crate::parser::ast::float_value_node::FloatValueNode;
+use crate::parser::ast::string_node::StringNode;
 use crate::parser::precedence::Precedence;
 
 pub type ParseFunc = fn(&mut Parser, bool) -> Result<Box<dyn AstNode>>;
 
-pub fn not_implemented_parser<'a>(_parser: &'a mut Parser, _v: bool) -> Result<Box<dyn AstNode>> {
+pub fn not_implemented_parser(_parser: &mut Parser, _v: bool) -> Result<Box<dyn AstNode>> {
     unimplemented!()
 }
 
 
-pub fn binary<'a>(parser: &'a mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+pub fn binary(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
     let operator_type = parser.get_previous().get_token_type();
     let rule = operator_type.get_rule();
 
@@ -29,20 +37,168 @@ pub fn binary<'a>(parser: &'a mut Parser, _can_assign: bool) -> Result<Box<dyn A
         TokenType::Minus => node.set_op(Operations::OpIntMinus),
         TokenType::Star => node.set_op(Operations::OpIntMul),
         TokenType::Slash => node.set_op(Operations::OpIntDiv),
+        TokenType::Percent => node.set_op(Operations::OpIntMod),
+        TokenType::LeftArrow => node.set_op(Operations::OpIntShiftLeft),
+        TokenType::RightArrow => node.set_op(Operations::OpIntShiftRight),
+        TokenType::And => node.set_op(Operations::OpIntAnd),
+        TokenType::Or => node.set_op(Operations::OpIntOr),
+        TokenType::Cap => node.set_op(Operations::OpIntXor),
+        TokenType::EqualEqual => node.set_op(Operations::OpIntEqual),
+        TokenType::NotEqual => node.set_op(Operations::OpIntNotEqual),
+        TokenType::Less => node.set_op(Operations::OpIntLess),
+        TokenType::LessEqual => node.set_op(Operations::OpIntLessEqual),
+        TokenType::Greater => node.set_op(Operations::OpIntGreater),
+        TokenType::GreaterEqual => node.set_op(Operations::OpIntGreaterEqual),
         others => unimplemented!("{:?}", others),
     }
 
     Ok(Box::new(node))
 }
 
-pub fn grouping<'a>(parser: &'a mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+pub fn logical(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+    let operator_type = parser.get_previous().get_token_type();
+    let rule = operator_type.get_rule();
+
+    let left_hand = parser.pop_ast();
+    let next_precedence = Precedence::try_from(rule.precedence.value() + 1).unwrap();
+    let right_hand = parser.parse_precedence(next_precedence)?;
+
+    let mut node = LogicalAstNode::new(left_hand, right_hand);
+
+    match operator_type {
+        TokenType::AndAnd => node.set_op(Operations::OpLogicalAnd),
+        TokenType::OrOr => node.set_op(Operations::OpLogicalOr),
+        others => unimplemented!("{:?}", others),
+    }
+
+    Ok(Box::new(node))
+}
+
+pub fn unary(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+    let operator_type = parser.get_previous().get_token_type();
+    let operand = parser.parse_precedence(Precedence::PrecUnary)?;
+
+    let mut node = UnaryAstNode::new(operand);
+
+    match operator_type {
+        TokenType::Not => node.set_op(Operations::OpLogicalNot),
+        TokenType::Plus => node.set_op(Operations::OpUnaryPlus),
+        TokenType::Minus => node.set_op(Operations::OpNegate),
+        TokenType::Wave => node.set_op(Operations::OpBitNot),
+        TokenType::PlusPlus => node.set_op(Operations::OpPreIncrement),
+        TokenType::MinusMinus => node.set_op(Operations::OpPreDecrement),
+        others => unimplemented!("{:?}", others),
+    }
+
+    Ok(Box::new(node))
+}
+
+pub fn postfix(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+    let operator_type = parser.get_previous().get_token_type();
+
+    let operand = parser.pop_ast();
+
+    let mut node = UnaryAstNode::new(operand);
+
+    match operator_type {
+        TokenType::PlusPlus => node.set_op(Operations::OpPostIncrement),
+        TokenType::MinusMinus => node.set_op(Operations::OpPostDecrement),
+        others => unimplemented!("{:?}", others),
+    }
+
+    Ok(Box::new(node))
+}
+
+pub fn assignment(parser: &mut Parser, can_assign: bool) -> Result<Box<dyn AstNode>> {
+    let operator_type = parser.get_previous().get_token_type();
+
+    let target = parser.pop_ast();
+    // whether the target designates an object is checked by the semantic analysis
+    if !can_assign {
+        return Err(parser.error("Invalid assignment target."));
+    }
+    // assignments are right associative: a = b = c is a = (b = c)
+    let value = parser.parse_precedence(Precedence::PrecAssignment)?;
+
+    let mut node = AssignmentNode::new(target, value);
+
+    match operator_type {
+        TokenType::Equal => node.set_op(Operations::OpAssign),
+        TokenType::PlusEqual => node.set_op(Operations::OpIntAdd),
+        TokenType::MinusEqual => node.set_op(Operations::OpIntMinus),
+        TokenType::StarEqual => node.set_op(Operations::OpIntMul),
+        TokenType::SlashEqual => node.set_op(Operations::OpIntDiv),
+        TokenType::AndEqual => node.set_op(Operations::OpIntAnd),
+        TokenType::OrEqual => node.set_op(Operations::OpIntOr),
+        TokenType::CapEqual => node.set_op(Operations::OpIntXor),
+        TokenType::PercentEqual => node.set_op(Operations::OpIntMod),
+        TokenType::LeftArrowEqual => node.set_op(Operations::OpIntShiftLeft),
+        TokenType::RightArrowEqual => node.set_op(Operations::OpIntShiftRight),
+        others => unimplemented!("{:?}", others),
+    }
+
+    Ok(Box::new(node))
+}
+
+pub fn call(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+    let callee = parser.pop_ast();
+    let Some(callee) = callee.downcast_ref::<IdentifierNode>() else {
+        return Err(parser.error("Called object is not a function."));
+    };
+
+    let mut args = vec!();
+    if !parser.check(TokenType::RightParen) {
+        loop {
+            args.push(parser.expression()?);
+            if !parser.t_match(TokenType::Comma)? {
+                break;
+            }
+        }
+    }
+    parser.consume(TokenType::RightParen, "Expected ')' after arguments.")?;
+
+    Ok(Box::new(CallNode::new(callee.name.clone(), callee.symbol, args)))
+}
+
+pub fn grouping(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
     let expression = parser.expression()?;
     parser.consume(TokenType::RightParen, "Expect ')' after expression.")?;
     Ok(expression)
 }
 
-pub fn int_number<'a>(parser: &'a mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+pub fn int_number(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
     let token = parser.get_previous();
-    Ok(Box::new(ValueNode::new(token.get_value() as i64)))
+    Ok(Box::new(ValueNode::new(token.get_value() as i64, token.get_data_type())))
 }
 
+pub fn float_number(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+    let token = parser.get_previous();
+    Ok(Box::new(FloatValueNode::new(token.get_float_value(), token.get_data_type())))
+}
+
+pub fn string(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+    // adjacent string literals form a single one
+    let mut value = parser.get_previous().get_bytes().to_vec();
+    while parser.t_match(TokenType::String)? {
+        value.extend_from_slice(parser.get_previous().get_bytes());
+    }
+    Ok(Box::new(StringNode::new(value)))
+}
+
+pub fn variable(parser: &mut Parser, _can_assign: bool) -> Result<Box<dyn AstNode>> {
+    let name = parser.get_previous().get_text().to_string();
+    let span = parser.identifier_span();
+    let symbol = parser.symbols().resolve(&name, span)?;
+
+    // functions can only be called, there are no function pointers
+    match (parser.symbols().get(symbol).is_function(), parser.check(TokenType::LeftParen)) {
+        (true, false) => return Err(CompilerError(span, format!(
+            "Function '{}' used as a value.", name
+        ))),
+        (false, true) => return Err(CompilerError(span, format!(
+            "Called object '{}' is not a function.", name
+        ))),
+        _ => {},
+    }
+    Ok(Box::new(IdentifierNode::new(name, symbol)))
+}
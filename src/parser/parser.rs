@@ -1,16 +1,31 @@
+use crate::common::errors::diagnostic::{Diagnostic, Severity};
 use crate::common::errors::error::CompilerErrorKind;
 use crate::common::errors::error::CompilerErrorKind::CompilerError;
+use crate::common::span::Span;
 use crate::scanner::tokens::{Token, TokenType};
 use crate::common::Result;
 use crate::parser::ast::block_node::BlockNode;
 use crate::parser::ast::core::AstNode;
+use crate::parser::ast::declaration_node::{DeclarationNode, Declarator};
+use crate::parser::ast::expression_statement_node::ExpressionStatementNode;
+use crate::parser::ast::do_while_node::DoWhileNode;
+use crate::parser::ast::for_node::ForNode;
+use crate::parser::ast::function_node::{FunctionNode, Parameter};
+use crate::parser::ast::if_node::IfNode;
+use crate::parser::ast::loop_control_node::LoopControlNode;
+use crate::parser::ast::while_node::WhileNode;
 use crate::parser::ast::print_node::PrintAstNode;
+use crate::parser::ast::program_node::ProgramNode;
+use crate::parser::ast::return_node::ReturnNode;
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
 use crate::parser::precedence::Precedence;
-use crate::scanner::scanner::Scanner;
+use crate::scanner::token_stream::TokenStream;
+use crate::symbols::symbol_table::{ScopeKind, Storage, SymbolTable};
 
 pub struct Parser<'a> {
 
-    scanner: &'a mut Scanner<'a>,
+    scanner: &'a mut dyn TokenStream,
 
 
     left_hand_stack: Vec<Box<dyn AstNode>>,
@@ -22,36 +37,95 @@ pub struct Parser<'a> {
     which should be "vector" "<" "pair" "<" "int" "," "int" ">" ">"
      */
     temp_token_stack: Vec<Token>,
-    /**
-    On token breaking cases, the column shift should be adjusted
-    */
-    column_shift: usize,
 
     previous: Token,
     current: Token,
 
+    /**
+    Declarations visible at the current point, identifiers are resolved while parsing
+    */
+    symbols: SymbolTable,
+
+    /**
+    Number of loops enclosing the statement being parsed, `break` / `continue` are only valid inside one
+    */
+    loop_depth: usize,
+
+    /**
+    Errors reported so far, parsing goes on after each to find the next one
+    */
+    diagnostics: Vec<Diagnostic>,
+
+    /**
+    Number of errors after which parsing stops, 0 for no limit
+    */
+    error_limit: usize,
+
+    /**
+    Whether a token of the statement being parsed failed to scan, the syntax errors its absence
+    causes are not reported
+    */
+    panic_mode: bool,
+
 }
 
+const DEFAULT_ERROR_LIMIT: usize = 20;
+
 impl<'a> Parser<'a> {
 
-    pub fn new(scanner: &'a mut Scanner<'a>) -> Parser<'a> {
+    pub fn new(scanner: &'a mut dyn TokenStream) -> Parser<'a> {
         let mut parser = Self {
             scanner,
 
             left_hand_stack: Vec::new(),
 
             temp_token_stack: Vec::new(),
-            column_shift: 0,
 
-            previous: Token::None,
-            current: Token::None,
+            previous: Token::none(),
+            current: Token::none(),
+
+            symbols: SymbolTable::new(),
+
+            loop_depth: 0,
+
+            diagnostics: Vec::new(),
+            error_limit: DEFAULT_ERROR_LIMIT,
+            panic_mode: false,
         };
-        parser.current = parser.scanner.scan().unwrap();
+        parser.current = parser.next_token();
         parser
     }
 
-    pub fn parse(&mut self) -> Result<Box<dyn AstNode>> {
-        self.block()
+    pub fn set_error_limit(&mut self, error_limit: usize) {
+        self.error_limit = error_limit;
+    }
+
+    /**
+    Parses the whole token stream, or reports every syntax error found in it
+    */
+    pub fn parse(&mut self) -> core::result::Result<Box<dyn AstNode>, Vec<Diagnostic>> {
+        let start = self.fetch_cur().span;
+        let mut declarations: Vec<Box<dyn AstNode>> = vec!();
+        while !self.check(TokenType::Eof) && !self.too_many_errors() {
+            let declaration_start = self.fetch_cur().span;
+            let declaration = self.function_definition();
+            match self.spanned(declaration_start, declaration) {
+                Ok(declaration) => {
+                    declarations.push(declaration);
+                    self.panic_mode = false;
+                },
+                Err(error) => self.recover(error, declaration_start, false),
+            }
+        }
+        if self.too_many_errors() {
+            self.diagnostics.push(Diagnostic::new(Severity::Note, String::from("Too many errors, stopping now.")));
+        }
+        if !self.diagnostics.is_empty() {
+            return Err(std::mem::take(&mut self.diagnostics));
+        }
+        let mut program: Box<dyn AstNode> = Box::new(ProgramNode::new(declarations));
+        program.set_span(start.to(self.fetch_cur().span));
+        Ok(program)
     }
 
     pub fn get_previous(&self) -> &Token {
@@ -62,6 +136,37 @@ impl<'a> Parser<'a> {
         self.fetch_cur()
     }
 
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
+
+    pub fn symbols_mut(&mut self) -> &mut SymbolTable {
+        &mut self.symbols
+    }
+
+    /**
+    Hands the symbol table over to the passes after parsing
+    */
+    pub fn take_symbols(&mut self) -> SymbolTable {
+        std::mem::replace(&mut self.symbols, SymbolTable::new())
+    }
+
+    /**
+    Span of the previous token, an identifier
+    */
+    pub fn identifier_span(&self) -> Span {
+        self.previous.span
+    }
+
+    /**
+    Gives a parsed node the span from `start` to the end of the previous token
+    */
+    pub fn spanned(&self, start: Span, node: Result<Box<dyn AstNode>>) -> Result<Box<dyn AstNode>> {
+        let mut node = node?;
+        node.set_span(start.to(self.previous.span));
+        Ok(node)
+    }
+
     pub fn push_ast(&mut self, ast: Box<dyn AstNode>) {
         self.left_hand_stack.push(ast);
     }
@@ -70,90 +175,230 @@ impl<'a> Parser<'a> {
         self.left_hand_stack.pop().unwrap()
     }
 
+    /**
+    Broken tokens carry their own spans, each a part of the token they were broken from
+    */
     fn push_broken_tokens(&mut self, token: Token) {
         self.temp_token_stack.push(token);
-        self.column_shift += 1;
     }
 
     fn pop_broken_tokens(&mut self) -> Token {
-        let token = self.temp_token_stack.pop().unwrap();
-        self.column_shift -= 1;
-        token
+        self.temp_token_stack.pop().unwrap()
+    }
+
+    /**
+    Error at the current token
+    */
+    pub fn error(&self, message: &'static str) -> CompilerErrorKind {
+        CompilerError(self.fetch_cur().span, String::from(message))
+    }
+
+    /**
+    Error for a missing token: a missing `;` or closing bracket was most likely forgotten at the
+    end of the previous token, which is suggested as a fix
+    */
+    fn missing(&self, token_type: TokenType, message: &'static str) -> CompilerErrorKind {
+        let text = match token_type {
+            TokenType::Semicolon => ";",
+            TokenType::RightParen => ")",
+            TokenType::RightBrace => "}",
+            _ => return self.error(message),
+        };
+        if self.previous.get_token_type() == TokenType::None {
+            return self.error(message);
+        }
+        Diagnostic::error(String::from(message))
+            .with_code("E0300")
+            .at(self.fetch_cur().span)
+            .with_insertion_after(self.previous.span, String::from(text))
+            .into()
+    }
+
+    fn too_many_errors(&self) -> bool {
+        self.error_limit != 0 && self.diagnostics.len() >= self.error_limit
+    }
+
+    /**
+    Reports an error of the declaration or statement starting at `start` and skips the rest of it,
+    once the error limit is reached errors are dropped and parsing winds up
+    */
+    fn recover(&mut self, error: CompilerErrorKind, start: Span, in_block: bool) {
+        if self.too_many_errors() {
+            return;
+        }
+        if !self.panic_mode {
+            self.report(error);
+        }
+        self.left_hand_stack.clear();
+
+        // skipping at least a token, else the same error comes again
+        if self.fetch_cur().span == start && !self.check(TokenType::Eof) {
+            self.advance();
+        }
+        self.synchronize(in_block);
+        self.panic_mode = false;
     }
 
-    fn get_position(&self) -> (usize, usize) {
-        let (line, column) = self.scanner.get_position();
-        (line, column + self.column_shift)
+    /**
+    <p> Panic mode: skips tokens up to the end of the broken declaration or statement, so that the
+    errors after it are reported on their own. </p>
+
+    Stops after a `;` or a block skipped as a whole, or before a type starting a declaration. In a
+    block it also stops before the `}` closing it and before a keyword starting a statement
+    */
+    fn synchronize(&mut self, in_block: bool) {
+        let mut depth = 0;
+        while !self.too_many_errors() {
+            let token_type = self.fetch_cur().get_token_type();
+            match token_type {
+                TokenType::Eof => return,
+                TokenType::RightBrace if depth == 0 && in_block => return,
+                TokenType::Semicolon if depth == 0 => {
+                    self.advance();
+                    return;
+                },
+                TokenType::RightBrace if depth <= 1 => {
+                    self.advance();
+                    return;
+                },
+                TokenType::LeftBrace => depth += 1,
+                TokenType::RightBrace => depth -= 1,
+                _ if depth == 0 && DataType::is_type_specifier(token_type) => return,
+                _ if depth == 0 && in_block && Self::starts_statement(token_type) => return,
+                _ => {},
+            }
+            self.advance();
+        }
+    }
+
+    fn starts_statement(token_type: TokenType) -> bool {
+        matches!(
+            token_type,
+            TokenType::Print | TokenType::If | TokenType::While | TokenType::Do | TokenType::For
+                | TokenType::Return | TokenType::Break | TokenType::Continue
+        )
     }
 
-    fn error(&self, message: &'static str) -> CompilerErrorKind {
-        let (line, column) = self.get_position();
-        CompilerError(line, column, String::from(message))
+    fn report(&mut self, error: CompilerErrorKind) {
+        if !self.too_many_errors() {
+            self.diagnostics.push(Diagnostic::from(error));
+        }
+    }
+
+    /**
+    Next token of the stream, tokens that fail to scan are reported and left out
+    */
+    fn next_token(&mut self) -> Token {
+        loop {
+            match self.scanner.scan() {
+                Ok(token) => return token,
+                Err(error) => {
+                    self.report(error);
+                    self.panic_mode = true;
+                },
+            }
+        }
     }
 
-    fn advance(&mut self) -> Result<()> {
-        self.previous = self.current.clone();
-        if self.temp_token_stack.len() > 0 {
-            self.pop_broken_tokens();
+    fn advance(&mut self) {
+        if !self.temp_token_stack.is_empty() {
+            self.previous = self.pop_broken_tokens();
         } else {
             self.previous = self.current.clone();
-            self.current = self.scanner.scan()?;
+            self.current = self.next_token();
         }
-        Ok(())
     }
 
     fn fetch_cur(&self) -> &Token {
-        if self.temp_token_stack.len() > 0 {
+        if !self.temp_token_stack.is_empty() {
             self.temp_token_stack.last().unwrap()
         } else {
             &self.current
         }
     }
 
-    fn check(&self, token_type: TokenType) -> bool {
+    pub fn check(&self, token_type: TokenType) -> bool {
         self.fetch_cur().get_token_type() == token_type
     }
 
     pub fn consume(&mut self, token_type: TokenType, msg: &'static str) -> Result<()> {
         if self.check(token_type) {
-            self.advance()?;
+            self.advance();
             return Ok(());
         }
+        Err(self.missing(token_type, msg))
+    }
+
+    pub fn consume_identifier(&mut self, msg: &'static str) -> Result<String> {
+        if self.check(TokenType::Identifier) {
+            let name = self.fetch_cur().get_text().to_string();
+            self.advance();
+            return Ok(name);
+        }
         Err(self.error(msg))
     }
 
-    fn t_match(&mut self, token_type: TokenType) -> Result<bool> {
+    fn match_data_type(&mut self) -> Result<Option<DataType>> {
+        let mut specifiers = vec!();
+        while DataType::is_type_specifier(self.fetch_cur().get_token_type()) {
+            specifiers.push(self.fetch_cur().get_token_type());
+            self.advance();
+        }
+        if specifiers.is_empty() {
+            return Ok(None);
+        }
+        match DataType::from_specifiers(&specifiers) {
+            Some(data_type) => Ok(Some(data_type)),
+            None => Err(self.error("Invalid combination of type specifiers.")),
+        }
+    }
+
+    /**
+    Applies the `*`s of a declarator to its base type, `char *` is the only pointer type
+    */
+    fn pointer_type(&mut self, base: DataType) -> Result<DataType> {
+        if !self.t_match(TokenType::Star)? {
+            return Ok(base);
+        }
+        if base != DataType::Char || self.check(TokenType::Star) {
+            return Err(self.error("Only 'char *' pointers are supported."));
+        }
+        Ok(DataType::CharPointer)
+    }
+
+    pub fn t_match(&mut self, token_type: TokenType) -> Result<bool> {
         if !self.check(token_type) {
             return Ok(false);
         }
-        self.advance()?;
+        self.advance();
         Ok(true)
     }
 
     /**********************************************************************************/
 
     pub fn parse_precedence(&mut self, precedence: Precedence) -> Result<Box<dyn AstNode>> {
-        self.advance()?;
+        self.advance();
         let can_assign = precedence.value() <= Precedence::PrecAssignment.value();
 
         let Some(prefix_rule) = self.get_previous().get_token_type().get_rule().get_prefix() else {
-            return Err(self.error("Expected expression."))
+            // the token taken for the expression
+            return Err(CompilerError(self.previous.span, String::from("Expected expression.")))
         };
 
-        let prefix = prefix_rule(self, can_assign)?;
+        // an expression spans from its first token, operands included
+        let start = self.previous.span;
+        let prefix = prefix_rule(self, can_assign);
+        let prefix = self.spanned(start, prefix)?;
         self.push_ast(prefix);
 
         while precedence.value() <= self.get_current().get_token_type().get_rule().precedence.value() {
-            self.advance()?;
+            self.advance();
             let infix_rule = self.get_previous().get_token_type().get_rule().get_infix().unwrap();
-            let infix = infix_rule(self, can_assign)?;
+            let infix = infix_rule(self, can_assign);
+            let infix = self.spanned(start, infix)?;
             self.push_ast(infix);
         }
 
-        if can_assign && self.t_match(TokenType::Equal)? {
-            return Err(self.error("Invalid assignment target."))
-        }
-
         Ok(self.pop_ast())
     }
 
@@ -162,24 +407,66 @@ impl<'a> Parser<'a> {
     }
 
     pub fn statement(&mut self) -> Result<Box<dyn AstNode>> {
+        let start = self.fetch_cur().span;
+        let statement = self.statement_kind();
+        self.spanned(start, statement)
+    }
+
+    fn statement_kind(&mut self) -> Result<Box<dyn AstNode>> {
         if self.t_match(TokenType::Print)? {
             self.print_statement()
-            // TODO: add int declaration
-        // } else if self.t_match(TokenType::KeyInt)? {
+        } else if self.t_match(TokenType::If)? {
+            self.if_statement()
+        } else if self.t_match(TokenType::While)? {
+            self.while_statement()
+        } else if self.t_match(TokenType::Do)? {
+            self.do_while_statement()
+        } else if self.t_match(TokenType::For)? {
+            self.for_statement()
+        } else if self.t_match(TokenType::Return)? {
+            self.return_statement()
+        } else if self.t_match(TokenType::Break)? {
+            self.loop_control_statement(Operations::OpBreak)
+        } else if self.t_match(TokenType::Continue)? {
+            self.loop_control_statement(Operations::OpContinue)
+        } else if self.check(TokenType::LeftBrace) {
+            self.block()
+        } else if self.t_match(TokenType::Semicolon)? {
+            // empty statement
+            Ok(Box::new(BlockNode::new(vec!())))
+        } else if let Some(data_type) = self.match_data_type()? {
+            self.variable_declaration(data_type)
         } else {
             self.expression_statement()
         }
     }
 
     pub fn block(&mut self) -> Result<Box<dyn AstNode>> {
+        self.symbols.enter_scope(ScopeKind::Block);
+        let block = self.block_body();
+        self.symbols.leave_scope();
+        block
+    }
+
+    /**
+    Statements of a block, declared into the current scope
+    */
+    fn block_body(&mut self) -> Result<Box<dyn AstNode>> {
 
         // temporary: consume a left brace {
 
         self.t_match(TokenType::LeftBrace)?;
 
         let mut asts: Vec<Box<dyn AstNode>> = vec!();
-        while !self.check(TokenType::RightBrace) && !self.t_match(TokenType::Eof)? {
-            asts.push(self.statement()?)
+        while !self.check(TokenType::RightBrace) && !self.check(TokenType::Eof) && !self.too_many_errors() {
+            let start = self.fetch_cur().span;
+            match self.statement() {
+                Ok(ast) => {
+                    asts.push(ast);
+                    self.panic_mode = false;
+                },
+                Err(error) => self.recover(error, start, true),
+            }
         }
         self.consume(TokenType::RightBrace, "Expect '}' after block")?;
 
@@ -188,20 +475,336 @@ impl<'a> Parser<'a> {
 
     /**********************************************************************************/
 
+    fn function_definition(&mut self) -> Result<Box<dyn AstNode>> {
+        let Some(return_type) = self.match_data_type()? else {
+            return Err(self.error("Expected function return type."));
+        };
+        let return_type = self.pointer_type(return_type)?;
+        let name = self.consume_identifier("Expected function name.")?;
+        let span = self.identifier_span();
+
+        // parameters live in the function scope, which the body shares
+        self.symbols.enter_scope(ScopeKind::Function);
+        let function = self.function_rest(name, span, return_type);
+        self.symbols.leave_scope();
+        function
+    }
+
+    fn function_rest(
+        &mut self,
+        name: String,
+        span: Span,
+        return_type: DataType,
+    ) -> Result<Box<dyn AstNode>> {
+        self.consume(TokenType::LeftParen, "Expected '(' after function name.")?;
+        let params = self.parameter_list()?;
+        self.consume(TokenType::RightParen, "Expected ')' after parameters.")?;
+
+        let is_definition = !self.t_match(TokenType::Semicolon)?;
+        if is_definition && !self.check(TokenType::LeftBrace) {
+            return Err(self.error("Expected '{' or ';' after function declarator."));
+        }
+
+        // declared before the body so that the function can call itself
+        let param_types = params.iter().map(|param| param.data_type).collect();
+        let symbol = self.symbols.declare_function(&name, return_type, param_types, is_definition, span)?;
+
+        let body = if is_definition {
+            let start = self.fetch_cur().span;
+            let body = self.block_body();
+            Some(self.spanned(start, body)?)
+        } else {
+            None
+        };
+
+        Ok(Box::new(FunctionNode::new(name, symbol, return_type, params, body)))
+    }
+
+    fn parameter_list(&mut self) -> Result<Vec<Parameter>> {
+        let mut params = vec!();
+        if self.check(TokenType::RightParen) {
+            return Ok(params);
+        }
+        loop {
+            let Some(data_type) = self.match_data_type()? else {
+                return Err(self.error("Expected parameter type."));
+            };
+            if data_type == DataType::Void {
+                // `(void)` declares an empty parameter list
+                if params.is_empty() && self.check(TokenType::RightParen) {
+                    return Ok(params);
+                }
+                return Err(self.error("Parameter declared void."));
+            }
+            let data_type = self.pointer_type(data_type)?;
+            let name = self.consume_identifier("Expected parameter name.")?;
+            let symbol = self.symbols.declare_variable(
+                &name,
+                data_type,
+                Storage::Parameter(params.len()),
+                self.identifier_span(),
+            )?;
+            params.push(Parameter::new(name, symbol, data_type));
+
+            if !self.t_match(TokenType::Comma)? {
+                break;
+            }
+        }
+        Ok(params)
+    }
+
+    fn return_statement(&mut self) -> Result<Box<dyn AstNode>> {
+        let value = if self.check(TokenType::Semicolon) {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        self.consume(TokenType::Semicolon, "Expected ';' after return value.")?;
+        Ok(Box::new(ReturnNode::new(value)))
+    }
+
     fn print_statement(&mut self) -> Result<Box<dyn AstNode>> {
         let ast = self.expression()?;
         self.consume(TokenType::Semicolon, "Expected ';' after expression.")?;
         Ok(Box::new(PrintAstNode::new(ast)))
     }
 
+    fn if_statement(&mut self) -> Result<Box<dyn AstNode>> {
+        self.consume(TokenType::LeftParen, "Expected '(' after 'if'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after condition.")?;
+
+        let then_branch = self.statement()?;
+        let else_branch = if self.t_match(TokenType::Else)? {
+            Some(self.statement()?)
+        } else {
+            None
+        };
+
+        Ok(Box::new(IfNode::new(condition, then_branch, else_branch)))
+    }
+
+    fn loop_body(&mut self) -> Result<Box<dyn AstNode>> {
+        self.loop_depth += 1;
+        let body = self.statement();
+        self.loop_depth -= 1;
+        body
+    }
+
+    fn while_statement(&mut self) -> Result<Box<dyn AstNode>> {
+        self.consume(TokenType::LeftParen, "Expected '(' after 'while'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after condition.")?;
+
+        let body = self.loop_body()?;
+
+        Ok(Box::new(WhileNode::new(condition, body)))
+    }
+
+    fn do_while_statement(&mut self) -> Result<Box<dyn AstNode>> {
+        let body = self.loop_body()?;
+
+        self.consume(TokenType::While, "Expected 'while' after 'do' body.")?;
+        self.consume(TokenType::LeftParen, "Expected '(' after 'while'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after condition.")?;
+        self.consume(TokenType::Semicolon, "Expected ';' after do-while statement.")?;
+
+        Ok(Box::new(DoWhileNode::new(body, condition)))
+    }
+
+    fn for_statement(&mut self) -> Result<Box<dyn AstNode>> {
+        // a declaration in the init clause is only visible inside the loop
+        self.symbols.enter_scope(ScopeKind::Block);
+        let for_node = self.for_clauses();
+        self.symbols.leave_scope();
+        for_node
+    }
+
+    fn for_clauses(&mut self) -> Result<Box<dyn AstNode>> {
+        self.consume(TokenType::LeftParen, "Expected '(' after 'for'.")?;
+
+        let init = if self.t_match(TokenType::Semicolon)? {
+            None
+        } else if let Some(data_type) = self.match_data_type()? {
+            Some(self.variable_declaration(data_type)?)
+        } else {
+            Some(self.expression_statement()?)
+        };
+
+        let condition = if self.check(TokenType::Semicolon) {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        self.consume(TokenType::Semicolon, "Expected ';' after loop condition.")?;
+
+        let step: Option<Box<dyn AstNode>> = if self.check(TokenType::RightParen) {
+            None
+        } else {
+            let start = self.fetch_cur().span;
+            let step: Box<dyn AstNode> = Box::new(ExpressionStatementNode::new(self.expression()?));
+            Some(self.spanned(start, Ok(step))?)
+        };
+        self.consume(TokenType::RightParen, "Expected ')' after for clauses.")?;
+
+        let body = self.loop_body()?;
+
+        Ok(Box::new(ForNode::new(init, condition, step, body)))
+    }
+
+    fn loop_control_statement(&mut self, op: Operations) -> Result<Box<dyn AstNode>> {
+        if self.loop_depth == 0 {
+            // at the keyword, the parser is past it
+            let message = match op {
+                Operations::OpBreak => "'break' statement not within a loop.",
+                _ => "'continue' statement not within a loop.",
+            };
+            return Err(CompilerError(self.previous.span, String::from(message)));
+        }
+        self.consume(TokenType::Semicolon, "Expected ';' after loop control statement.")?;
+
+        let mut node = LoopControlNode::new();
+        node.set_op(op);
+        Ok(Box::new(node))
+    }
+
     fn expression_statement(&mut self) -> Result<Box<dyn AstNode>> {
         let ast = self.expression()?;
         self.consume(TokenType::Semicolon, "Expected ';' after expression.")?;
-        Ok(ast)
+        Ok(Box::new(ExpressionStatementNode::new(ast)))
     }
 
-    // fn variable_declaration(&mut self, token_type: &TokenType) -> Result<Box<dyn AstNode>> {
-    //
-    // }
+    fn variable_declaration(&mut self, base_type: DataType) -> Result<Box<dyn AstNode>> {
+        if base_type == DataType::Void {
+            return Err(self.error("Variable declared void."));
+        }
+        let mut declarators = vec!();
+        loop {
+            let data_type = self.pointer_type(base_type)?;
+            let name = self.consume_identifier("Expected variable name.")?;
+            // the name is in scope from its own initializer on
+            let symbol = self.symbols.declare_variable(
+                &name,
+                data_type,
+                Storage::Local,
+                self.identifier_span(),
+            )?;
+            let init = if self.t_match(TokenType::Equal)? {
+                Some(self.expression()?)
+            } else {
+                None
+            };
+            declarators.push(Declarator::new(name, symbol, data_type, init));
+
+            if !self.t_match(TokenType::Comma)? {
+                break;
+            }
+        }
+        self.consume(TokenType::Semicolon, "Expected ';' after variable declaration.")?;
+        Ok(Box::new(DeclarationNode::new(base_type, declarators)))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::scanner::Scanner;
+    use crate::source_code::SourceCode;
+
+    #[test]
+    fn test_spans() {
+        let text = "int main() {\n  return 1 +\n    2;\n}\n";
+        let source_code = SourceCode::new(text.to_string());
+        let mut scanner = Scanner::new(&source_code);
+        let mut parser = Parser::new(&mut scanner);
+        let program = parser.parse().unwrap();
+        let program = program.downcast_ref::<ProgramNode>().unwrap();
+        let function = program.declarations[0].downcast_ref::<FunctionNode>().unwrap();
+        let body = function.body.as_ref().unwrap().downcast_ref::<BlockNode>().unwrap();
+        let statement = body.block[0].downcast_ref::<ReturnNode>().unwrap();
+
+        let source = |node: &dyn AstNode| &text[node.get_span().start..node.get_span().end];
+        assert_eq!(source(function), text.trim_end());
+        assert_eq!(source(statement), "return 1 +\n    2;");
+        assert_eq!(source(statement.value.as_deref().unwrap()), "1 +\n    2");
+        assert_eq!((statement.get_span().line, statement.get_span().column), (2, 3));
+    }
+
+    #[test]
+    fn test_error_position() {
+        let source_code = SourceCode::new(String::from("int main() {\n  int x = 1\n  x = 2;\n}"));
+        let mut scanner = Scanner::new(&source_code);
+        let mut parser = Parser::new(&mut scanner);
+        // reported at the token where ';' is missing, not where the scanner is, with a fix-it after
+        // the previous token
+        let diagnostic = parser.parse().unwrap_err().remove(0);
+        let span = diagnostic.primary_span().unwrap();
+        assert_eq!((span.line, span.column), (3, 3));
+        assert_eq!((diagnostic.fix_its[0].span.start, diagnostic.fix_its[0].replacement.as_str()), (24, ";"));
+    }
+
+    fn errors(text: &str, error_limit: usize) -> Vec<(usize, usize, String)> {
+        let source_code = SourceCode::new(text.to_string());
+        let mut scanner = Scanner::new(&source_code);
+        let mut parser = Parser::new(&mut scanner);
+        parser.set_error_limit(error_limit);
+        parser.parse().unwrap_err().into_iter()
+            .map(|diagnostic| {
+                let span = diagnostic.primary_span().unwrap_or_default();
+                (span.line, span.column, diagnostic.message)
+            })
+            .collect()
+    }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_recovery() {
+        let text = "
+int f(int a) {
+  a = (a + 2;
+  if (a > 1 { a = 3; }
+  return a + ;
+  while (a) { a = a - 1 }
+}
+int g(int a, 3) { return a; }
+}
+int main() { return f(1) }";
+        assert_eq!(errors(text, 0), vec!(
+            (3, 13, String::from("Expect ')' after expression.")),
+            (4, 13, String::from("Expected ')' after condition.")),
+            (5, 14, String::from("Expected expression.")),
+            (6, 25, String::from("Expected ';' after expression.")),
+            (8, 14, String::from("Expected parameter type.")),
+            (9, 1, String::from("Expected function return type.")),
+            (10, 26, String::from("Expected ';' after return value.")),
+        ));
+    }
+
+    #[test]
+    fn test_scanner_errors() {
+        // the tokens left out for scanner errors do not cause syntax errors of their own
+        let text = "int main() {\n  int a = 2 @ 3;\n  a = a $;\n  return a + 1 }";
+        assert_eq!(errors(text, 0), vec!(
+            (2, 13, String::from("Unrecognized character '@'.")),
+            (3, 9, String::from("Unrecognized character '$'.")),
+            (4, 16, String::from("Expected ';' after return value.")),
+        ));
+    }
+
+    #[test]
+    fn test_loop_control_outside_loop() {
+        let text = "int main() {\n  while (1) { break; }\n  break;\n  if (1) continue;\n}";
+        assert_eq!(errors(text, 0), vec!(
+            (3, 3, String::from("'break' statement not within a loop.")),
+            (4, 10, String::from("'continue' statement not within a loop.")),
+        ));
+    }
+
+    #[test]
+    fn test_error_limit() {
+        let errors = errors("int main() { 1 + ; 2 + ; 3 + ; }", 2);
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[2].2, "Too many errors, stopping now.");
+    }
+}
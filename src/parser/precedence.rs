@@ -1,5 +1,5 @@
 use num_enum::TryFromPrimitive;
-use crate::parser::parse_func::{binary, grouping, int_number, not_implemented_parser, ParseFunc};
+use crate::parser::parse_func::{assignment, binary, call, float_number, grouping, int_number, string, logical, not_implemented_parser, postfix, unary, variable, ParseFunc};
 use crate::scanner::tokens::TokenType;
 
 #[derive(Clone, Copy, Debug, PartialOrd, PartialEq, TryFromPrimitive)]
@@ -10,12 +10,14 @@ pub enum Precedence {
     PrecAssignment,
     PrecOr,
     PrecAnd,
+    PrecBitOr,
+    PrecBitXor,
+    PrecBitAnd,
     PrecEquality,
     PrecComparison,
+    PrecShift,
     PrecTerm,
     PrecFactor,
-    PrecBitOr,
-    PrecBitAnd,
     PrecUnary,
     PrecCall,
     PrecPrimary,
@@ -45,7 +47,7 @@ pub struct ParseRule {
 impl ParseRule {
 
     pub fn get_prefix(&self) -> Option<ParseFunc> {
-        if self.prefix == not_implemented_parser {
+        if std::ptr::fn_addr_eq(self.prefix, not_implemented_parser as ParseFunc) {
             None
         } else {
             Some(self.prefix)
@@ -53,7 +55,7 @@ impl ParseRule {
     }
 
     pub fn get_infix(&self) -> Option<ParseFunc> {
-        if self.infix == not_implemented_parser {
+        if std::ptr::fn_addr_eq(self.infix, not_implemented_parser as ParseFunc) {
             None
         } else {
             Some(self.infix)
@@ -71,14 +73,48 @@ impl TokenType {
             TokenType::Minus => PARSE_RULE_MINUS,
             TokenType::Star => PARSE_RULE_STAR,
             TokenType::Slash => PARSE_RULE_SLASH,
-            TokenType::Integer => PARSE_RULE_INTEGER,
+            TokenType::Percent => PARSE_RULE_PERCENT,
+            TokenType::And => PARSE_RULE_AMPERSAND,
+            TokenType::Or => PARSE_RULE_PIPE,
+            TokenType::Cap => PARSE_RULE_CAP,
+            TokenType::LeftArrow => PARSE_RULE_LEFT_ARROW,
+            TokenType::RightArrow => PARSE_RULE_RIGHT_ARROW,
+            TokenType::Integer | TokenType::Char => PARSE_RULE_INTEGER,
+            TokenType::String => PARSE_RULE_STRING,
+            TokenType::Double | TokenType::Float => PARSE_RULE_FLOATING,
+            TokenType::Identifier => PARSE_RULE_IDENTIFIER,
+            TokenType::Not => PARSE_RULE_BANG,
+            TokenType::Wave => PARSE_RULE_WAVE,
+            TokenType::PlusPlus => PARSE_RULE_PLUS_PLUS,
+            TokenType::MinusMinus => PARSE_RULE_MINUS_MINUS,
+            TokenType::AndAnd => PARSE_RULE_AND_AND,
+            TokenType::OrOr => PARSE_RULE_OR_OR,
+            TokenType::EqualEqual => PARSE_RULE_EQUAL_EQUAL,
+            TokenType::NotEqual => PARSE_RULE_BANG_EQUAL,
+            TokenType::Greater => PARSE_RULE_GREATER,
+            TokenType::GreaterEqual => PARSE_RULE_GREATER_EQUAL,
+            TokenType::Less => PARSE_RULE_LESS,
+            TokenType::LessEqual => PARSE_RULE_LESS_EQUAL,
+            TokenType::Equal => PARSE_RULE_EQUAL,
+            TokenType::PlusEqual => PARSE_RULE_PLUS_EQUAL,
+            TokenType::MinusEqual => PARSE_RULE_MINUS_EQUAL,
+            TokenType::StarEqual => PARSE_RULE_STAR_EQUAL,
+            TokenType::SlashEqual => PARSE_RULE_SLASH_EQUAL,
+            TokenType::AndEqual => PARSE_RULE_AND_EQUAL,
+            TokenType::OrEqual => PARSE_RULE_OR_EQUAL,
+            TokenType::CapEqual => PARSE_RULE_CAP_EQUAL,
+            TokenType::PercentEqual => PARSE_RULE_PERCENT_EQUAL,
+            TokenType::LeftArrowEqual => PARSE_RULE_LEFT_ARROW_EQUAL,
+            TokenType::RightArrowEqual => PARSE_RULE_RIGHT_ARROW_EQUAL,
+            TokenType::Comma => PARSE_RULE_COMMA,
 
             TokenType::LeftBrace => PARSE_RULE_LEFT_BRACE,
             TokenType::RightBrace => PARSE_RULE_RIGHT_BRACE,
 
             TokenType::Semicolon => PARSE_RULE_SEMICOLON,
             TokenType::Eof => PARSE_RULE_EOF,
-            others=> unimplemented!("{:?}", others),
+            // keywords and the like neither start nor continue an expression
+            _ => PARSE_RULE_NONE,
         }
     }
 
@@ -86,7 +122,7 @@ impl TokenType {
 
 static PARSE_RULE_LEFT_PARAN: ParseRule = ParseRule {
     prefix: grouping,
-    infix: not_implemented_parser,
+    infix: call,
     precedence: Precedence::PrecCall,
 };
 
@@ -133,17 +169,35 @@ static PARSE_RULE_DOT: ParseRule = ParseRule {
 };
 
 static PARSE_RULE_MINUS: ParseRule = ParseRule {
-    prefix: not_implemented_parser,
+    prefix: unary,
     infix: binary,
     precedence: Precedence::PrecTerm,
 };
 
 static PARSE_RULE_PLUS: ParseRule = ParseRule {
-    prefix: not_implemented_parser,
+    prefix: unary,
     infix: binary,
     precedence: Precedence::PrecTerm,
 };
 
+static PARSE_RULE_PLUS_PLUS: ParseRule = ParseRule {
+    prefix: unary,
+    infix: postfix,
+    precedence: Precedence::PrecCall,
+};
+
+static PARSE_RULE_MINUS_MINUS: ParseRule = ParseRule {
+    prefix: unary,
+    infix: postfix,
+    precedence: Precedence::PrecCall,
+};
+
+static PARSE_RULE_NONE: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: not_implemented_parser,
+    precedence: Precedence::PrecNone,
+};
+
 static PARSE_RULE_SEMICOLON: ParseRule = ParseRule {
     prefix: not_implemented_parser,
     infix: not_implemented_parser,
@@ -164,90 +218,174 @@ static PARSE_RULE_STAR: ParseRule = ParseRule {
 
 static PARSE_RULE_PERCENT: ParseRule = ParseRule {
     prefix: not_implemented_parser,
+    infix: binary,
+    precedence: Precedence::PrecFactor,
+};
+
+static PARSE_RULE_BANG: ParseRule = ParseRule {
+    prefix: unary,
     infix: not_implemented_parser,
     precedence: Precedence::PrecNone,
 };
 
-static PARSE_RULE_BANG: ParseRule = ParseRule {
-    prefix: not_implemented_parser,
+static PARSE_RULE_WAVE: ParseRule = ParseRule {
+    prefix: unary,
     infix: not_implemented_parser,
     precedence: Precedence::PrecNone,
 };
 
 static PARSE_RULE_BANG_EQUAL: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecEquality,
 };
 
 static PARSE_RULE_EQUAL: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_PLUS_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_MINUS_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_STAR_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_SLASH_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_AND_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_OR_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_CAP_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_PERCENT_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_LEFT_ARROW_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
+};
+
+static PARSE_RULE_RIGHT_ARROW_EQUAL: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: assignment,
+    precedence: Precedence::PrecAssignment,
 };
 
 static PARSE_RULE_EQUAL_EQUAL: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecEquality,
 };
 
 static PARSE_RULE_GREATER: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecComparison,
 };
 
 static PARSE_RULE_GREATER_EQUAL: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecComparison,
 };
 
 static PARSE_RULE_LESS: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecComparison,
 };
 
 static PARSE_RULE_LESS_EQUAL: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecComparison,
 };
 
 static PARSE_RULE_AMPERSAND: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecBitAnd,
+};
+
+static PARSE_RULE_AND_AND: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: logical,
+    precedence: Precedence::PrecAnd,
+};
+
+static PARSE_RULE_OR_OR: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: logical,
+    precedence: Precedence::PrecOr,
+};
+
+static PARSE_RULE_PIPE: ParseRule = ParseRule {
+    prefix: not_implemented_parser,
+    infix: binary,
+    precedence: Precedence::PrecBitOr,
 };
 
 static PARSE_RULE_CAP: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecBitXor,
 };
 
 static PARSE_RULE_LEFT_ARROW: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecShift,
 };
 
 static PARSE_RULE_RIGHT_ARROW: ParseRule = ParseRule {
     prefix: not_implemented_parser,
-    infix: not_implemented_parser,
-    precedence: Precedence::PrecNone,
+    infix: binary,
+    precedence: Precedence::PrecShift,
 };
 
 static PARSE_RULE_IDENTIFIER: ParseRule = ParseRule {
-    prefix: not_implemented_parser,
+    prefix: variable,
     infix: not_implemented_parser,
     precedence: Precedence::PrecNone,
 };
 
 static PARSE_RULE_STRING: ParseRule = ParseRule {
-    prefix: not_implemented_parser,
+    prefix: string,
     infix: not_implemented_parser,
     precedence: Precedence::PrecNone,
 };
@@ -258,6 +396,12 @@ static PARSE_RULE_INTEGER: ParseRule = ParseRule {
     precedence: Precedence::PrecNone,
 };
 
+static PARSE_RULE_FLOATING: ParseRule = ParseRule {
+    prefix: float_number,
+    infix: not_implemented_parser,
+    precedence: Precedence::PrecNone,
+};
+
 static PARSE_RULE_EOF: ParseRule = ParseRule {
     prefix: not_implemented_parser,
     infix: not_implemented_parser,
@@ -0,0 +1,232 @@
+use crate::common::errors::error::CompilerErrorKind;
+use crate::common::Result;
+use crate::preprocessor::pp_token::PpToken;
+use crate::scanner::tokens::TokenType;
+
+/**
+ Value of a preprocessor expression: integers are computed as intmax_t or uintmax_t, both 64 bits
+ */
+#[derive(Clone, Copy)]
+struct Value {
+    bits: u64,
+    unsigned: bool,
+}
+
+impl Value {
+
+    fn boolean(value: bool) -> Value {
+        Self {
+            bits: value as u64,
+            unsigned: false,
+        }
+    }
+
+    fn is_true(&self) -> bool {
+        self.bits != 0
+    }
+
+}
+
+/**
+ Evaluates the controlling expression of `#if` or `#elif`. It is macro expanded and its
+ `defined` operators replaced already, the identifiers left stand for 0.
+ */
+pub fn evaluate(tokens: &[PpToken], directive: &PpToken) -> Result<bool> {
+    if tokens.is_empty() {
+        return Err(error(directive, String::from("#if with no expression.")));
+    }
+    let mut evaluator = Evaluator {
+        tokens,
+        index: 0,
+        evaluated: true,
+    };
+    let value = evaluator.conditional()?;
+    if let Some(token) = tokens.get(evaluator.index) {
+        return Err(error(token, format!("Missing binary operator before token '{}'.", token.spelling)));
+    }
+    Ok(value.is_true())
+}
+
+fn error(at: &PpToken, message: String) -> CompilerErrorKind {
+    CompilerErrorKind::PreprocessorError(at.span(), message)
+}
+
+/**
+ Binding strength of the binary operators, all of them are left associative
+ */
+fn precedence(token_type: TokenType) -> Option<usize> {
+    match token_type {
+        TokenType::OrOr => Some(1),
+        TokenType::AndAnd => Some(2),
+        TokenType::Or => Some(3),
+        TokenType::Cap => Some(4),
+        TokenType::And => Some(5),
+        TokenType::EqualEqual | TokenType::NotEqual => Some(6),
+        TokenType::Less | TokenType::Greater | TokenType::LessEqual | TokenType::GreaterEqual => Some(7),
+        TokenType::LeftArrow | TokenType::RightArrow => Some(8),
+        TokenType::Plus | TokenType::Minus => Some(9),
+        TokenType::Star | TokenType::Slash | TokenType::Percent => Some(10),
+        _ => None,
+    }
+}
+
+struct Evaluator<'a> {
+    tokens: &'a [PpToken],
+    index: usize,
+    /**
+    Cleared in the operand of `&&`, `||` or `?:` that is not evaluated, division by zero is fine
+    there
+    */
+    evaluated: bool,
+}
+
+impl Evaluator<'_> {
+
+    /**
+    `?:`, below the binary operators and right associative
+    */
+    fn conditional(&mut self) -> Result<Value> {
+        let condition = self.binary(1)?;
+        if !self.tokens.get(self.index).is_some_and(|token| token.is(TokenType::Question)) {
+            return Ok(condition);
+        }
+        self.index += 1;
+
+        let evaluated = self.evaluated;
+        self.evaluated = evaluated && condition.is_true();
+        let then = self.conditional();
+        self.evaluated = evaluated;
+        let then = then?;
+        match self.tokens.get(self.index) {
+            Some(colon) if colon.is(TokenType::Colon) => self.index += 1,
+            other => {
+                let at = other.unwrap_or(self.tokens.last().unwrap());
+                return Err(error(at, String::from("Missing ':' in expression.")));
+            },
+        }
+        self.evaluated = evaluated && !condition.is_true();
+        let otherwise = self.conditional();
+        self.evaluated = evaluated;
+        let otherwise = otherwise?;
+
+        // the usual arithmetic conversions between the two operands
+        let value = if condition.is_true() { then } else { otherwise };
+        Ok(Value { unsigned: then.unsigned || otherwise.unsigned, ..value })
+    }
+
+    fn binary(&mut self, min_precedence: usize) -> Result<Value> {
+        let mut left = self.unary()?;
+        while let Some(operator) = self.tokens.get(self.index) {
+            let token_type = operator.token.get_token_type();
+            let Some(precedence) = precedence(token_type).filter(|p| *p >= min_precedence) else {
+                break;
+            };
+            self.index += 1;
+
+            let right = if matches!(token_type, TokenType::AndAnd | TokenType::OrOr) {
+                let needed = left.is_true() == (token_type == TokenType::AndAnd);
+                let evaluated = self.evaluated;
+                self.evaluated &= needed;
+                let right = self.binary(precedence + 1);
+                self.evaluated = evaluated;
+                right?
+            } else {
+                self.binary(precedence + 1)?
+            };
+            left = self.apply(operator, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Value> {
+        let Some(token) = self.tokens.get(self.index) else {
+            let last = self.tokens.last().unwrap();
+            return Err(error(last, String::from("Expected value in expression.")));
+        };
+        self.index += 1;
+
+        match token.token.get_token_type() {
+            TokenType::Plus => self.unary(),
+            TokenType::Minus => {
+                let value = self.unary()?;
+                Ok(Value { bits: value.bits.wrapping_neg(), ..value })
+            },
+            TokenType::Wave => {
+                let value = self.unary()?;
+                Ok(Value { bits: !value.bits, ..value })
+            },
+            TokenType::Not => Ok(Value::boolean(!self.unary()?.is_true())),
+            TokenType::LeftParen => {
+                let value = self.conditional()?;
+                match self.tokens.get(self.index) {
+                    Some(close) if close.is(TokenType::RightParen) => {
+                        self.index += 1;
+                        Ok(value)
+                    },
+                    other => {
+                        let at = other.unwrap_or(self.tokens.last().unwrap());
+                        Err(error(at, String::from("Missing ')' in expression.")))
+                    },
+                }
+            },
+            TokenType::Integer | TokenType::Char => Ok(Value {
+                bits: token.token.get_value(),
+                unsigned: token.token.get_data_type().is_unsigned(),
+            }),
+            TokenType::Double | TokenType::Float => {
+                Err(error(token, String::from("Floating constant in preprocessor expression.")))
+            },
+            TokenType::Invalid => {
+                Err(error(token, String::from_utf8_lossy(token.token.get_bytes()).into_owned()))
+            },
+            // identifiers that are not macros, keywords included
+            _ if token.name().is_some() => Ok(Value::boolean(false)),
+            _ => Err(error(token, format!("Token '{}' is not valid in preprocessor expressions.", token.spelling))),
+        }
+    }
+
+    fn apply(&self, operator: &PpToken, left: Value, right: Value) -> Result<Value> {
+        // the usual arithmetic conversions: unsigned if either operand is
+        let unsigned = left.unsigned || right.unsigned;
+        let (x, y) = (left.bits, right.bits);
+        let arithmetic = |bits: u64| Value { bits, unsigned };
+
+        let value = match operator.token.get_token_type() {
+            TokenType::Star => arithmetic(x.wrapping_mul(y)),
+            TokenType::Slash | TokenType::Percent if y == 0 => {
+                if self.evaluated {
+                    return Err(error(operator, String::from("Division by zero in preprocessor expression.")));
+                }
+                arithmetic(0)
+            },
+            TokenType::Slash if unsigned => arithmetic(x / y),
+            TokenType::Slash => arithmetic((x as i64).wrapping_div(y as i64) as u64),
+            TokenType::Percent if unsigned => arithmetic(x % y),
+            TokenType::Percent => arithmetic((x as i64).wrapping_rem(y as i64) as u64),
+            TokenType::Plus => arithmetic(x.wrapping_add(y)),
+            TokenType::Minus => arithmetic(x.wrapping_sub(y)),
+            // shifts have the type of their left operand
+            TokenType::LeftArrow => Value { bits: x.wrapping_shl(y as u32), ..left },
+            TokenType::RightArrow if left.unsigned => Value { bits: x.wrapping_shr(y as u32), ..left },
+            TokenType::RightArrow => Value { bits: (x as i64).wrapping_shr(y as u32) as u64, ..left },
+            TokenType::Less if unsigned => Value::boolean(x < y),
+            TokenType::Less => Value::boolean((x as i64) < (y as i64)),
+            TokenType::Greater if unsigned => Value::boolean(x > y),
+            TokenType::Greater => Value::boolean((x as i64) > (y as i64)),
+            TokenType::LessEqual if unsigned => Value::boolean(x <= y),
+            TokenType::LessEqual => Value::boolean((x as i64) <= (y as i64)),
+            TokenType::GreaterEqual if unsigned => Value::boolean(x >= y),
+            TokenType::GreaterEqual => Value::boolean((x as i64) >= (y as i64)),
+            TokenType::EqualEqual => Value::boolean(x == y),
+            TokenType::NotEqual => Value::boolean(x != y),
+            TokenType::And => arithmetic(x & y),
+            TokenType::Cap => arithmetic(x ^ y),
+            TokenType::Or => arithmetic(x | y),
+            TokenType::AndAnd => Value::boolean(left.is_true() && right.is_true()),
+            TokenType::OrOr => Value::boolean(left.is_true() || right.is_true()),
+            other => unreachable!("{:?} is not a binary operator", other),
+        };
+        Ok(value)
+    }
+
+}
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+use crate::preprocessor::pp_token::{spell, PpToken};
+use crate::scanner::tokens::{Token, TokenType};
+
+/**
+ Macros whose replacement depends on where they are used
+ */
+#[derive(Clone, Copy, PartialEq)]
+pub enum Builtin {
+    File,
+    Line,
+}
+
+/**
+ <p> A `#define`d macro. </p>
+
+ Object-like macros have no parameter list, function-like ones have one, possibly empty.
+ */
+#[derive(Clone)]
+pub struct Macro {
+    pub params: Option<Vec<String>>,
+    pub body: Vec<PpToken>,
+    pub builtin: Option<Builtin>,
+}
+
+impl Macro {
+
+    pub fn new(params: Option<Vec<String>>, body: Vec<PpToken>) -> Macro {
+        Self {
+            params,
+            body,
+            builtin: None,
+        }
+    }
+
+    pub fn builtin(builtin: Builtin) -> Macro {
+        Self {
+            params: None,
+            body: vec!(),
+            builtin: Some(builtin),
+        }
+    }
+
+    /**
+    Index of the parameter a body token names
+    */
+    pub fn param_index(&self, token: &PpToken) -> Option<usize> {
+        let name = token.name()?;
+        self.params.as_ref()?.iter().position(|param| param == name)
+    }
+
+    /**
+    Whether a redefinition is the same, only those are allowed: the same parameters and the
+    same body tokens, separated by whitespace at the same places
+    */
+    pub fn same_definition(&self, other: &Macro) -> bool {
+        self.params == other.params
+            && self.builtin == other.builtin
+            && self.body.len() == other.body.len()
+            && self.body.iter().zip(other.body.iter()).enumerate().all(|(i, (a, b))| {
+                a.spelling == b.spelling && (i == 0 || a.leading_space == b.leading_space)
+            })
+    }
+
+}
+
+/**
+ The `#` operator: a string literal spelling the argument, whitespace between its tokens
+ becomes a single space
+ */
+pub fn stringify(argument: &[PpToken], at: &PpToken) -> PpToken {
+    let text = spell(argument);
+    PpToken {
+        spelling: quote(&text),
+        token: Token::string_token(text.into_bytes()).with_span(at.span()),
+        hide_set: HashSet::new(),
+        ..at.clone()
+    }
+}
+
+/**
+ Spelling of a string literal with the given value
+ */
+pub fn quote(text: &str) -> String {
+    let mut spelling = String::from("\"");
+    for c in text.chars() {
+        if c == '"' || c == '\\' {
+            spelling.push('\\');
+        }
+        spelling.push(c);
+    }
+    spelling.push('"');
+    spelling
+}
+
+/**
+ Whether a token is the `#` operator of a function-like macro body
+ */
+pub fn is_stringify(definition: &Macro, token: &PpToken) -> bool {
+    definition.params.is_some() && token.is(TokenType::Hash)
+}
@@ -0,0 +1,5 @@
+pub mod preprocessor;
+pub mod pp_token;
+pub mod macros;
+pub mod expression;
+pub mod predefined;
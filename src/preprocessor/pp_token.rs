@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+use crate::common::errors::diagnostic::Diagnostic;
+use crate::common::errors::error::CompilerErrorKind;
+use crate::common::span::Span;
+use crate::scanner::tokens::{Token, TokenType};
+use crate::source_code::SourceCode;
+
+/**
+ <p> A token as seen by the preprocessor: the scanned token along with its spelling and the
+ whitespace around it. </p>
+
+ Tokens of a macro expansion are placed at the macro name.
+ */
+#[derive(Clone, Debug)]
+pub struct PpToken {
+    pub token: Token,
+    /**
+    Source text of the token, pasting and stringification work on it
+    */
+    pub spelling: String,
+    /**
+    First token of a line, where a `#` starts a directive
+    */
+    pub line_start: bool,
+    pub leading_space: bool,
+    /**
+    Macros that must not be expanded from this token again, the C standard's hide set
+    */
+    pub hide_set: HashSet<String>,
+}
+
+impl PpToken {
+
+    pub fn eof(at: Span) -> PpToken {
+        Self {
+            token: Token::single_token(TokenType::Eof).with_span(at),
+            spelling: String::new(),
+            line_start: true,
+            leading_space: false,
+            hide_set: HashSet::new(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        self.token.span
+    }
+
+    pub fn is(&self, token_type: TokenType) -> bool {
+        self.token.get_token_type() == token_type
+    }
+
+    /**
+    Name of an identifier, keywords included: macros may be named after them
+    */
+    pub fn name(&self) -> Option<&str> {
+        self.spelling
+            .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            .then_some(self.spelling.as_str())
+    }
+
+    /**
+    Places the token at another position, e.g. the macro name a body token is expanded from
+    */
+    pub fn relocate(&mut self, to: &PpToken) {
+        self.token.span = to.span();
+    }
+
+}
+
+/**
+ Spelling of a token sequence, whitespace between tokens becomes a single space
+ */
+pub fn spell(tokens: &[PpToken]) -> String {
+    let mut text = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && token.leading_space {
+            text.push(' ');
+        }
+        text.push_str(&token.spelling);
+    }
+    text
+}
+
+/**
+ Splits a source text into preprocessing tokens, the last one is Eof.
+
+ Scanner errors become Invalid tokens placed at the error: they may be in a group skipped by
+ conditional compilation, or be completed by `##`. Scanner warnings are added to `diagnostics`.
+ */
+pub fn tokenize(text: String, file: usize, diagnostics: &mut Vec<Diagnostic>) -> Vec<PpToken> {
+    let source_code = SourceCode::new(text);
+    let mut scanner = source_code.into_scanner();
+    scanner.set_file(file);
+    let mut tokens = vec!();
+    loop {
+        let token = match scanner.scan() {
+            Ok(token) if token.get_token_type() == TokenType::Eof => {
+                tokens.push(PpToken::eof(token.span));
+                diagnostics.append(&mut scanner.take_diagnostics());
+                return tokens;
+            },
+            Ok(token) => token,
+            Err(CompilerErrorKind::ScannerError(span, message)) => Token::invalid_token(message).with_span(span),
+            Err(error) => unreachable!("{:?}", error),
+        };
+        tokens.push(PpToken {
+            token,
+            spelling: scanner.get_spelling(),
+            line_start: scanner.is_line_start(),
+            leading_space: scanner.has_leading_space(),
+            hide_set: HashSet::new(),
+        });
+    }
+}
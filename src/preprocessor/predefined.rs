@@ -0,0 +1,68 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/**
+ Object-like macros defined before any source is read, as name and replacement. `__FILE__` and
+ `__LINE__` depend on where they are used and are built into the preprocessor.
+ */
+pub fn predefined_macros() -> Vec<(&'static str, String)> {
+    let (date, time) = date_and_time(compilation_time());
+    vec![
+        ("__STDC__", String::from("1")),
+        ("__STDC_VERSION__", String::from("201112L")),
+        ("__STDC_HOSTED__", String::from("1")),
+        ("__x86_64__", String::from("1")),
+        ("__x86_64", String::from("1")),
+        ("__linux__", String::from("1")),
+        ("__linux", String::from("1")),
+        ("__DATE__", format!("\"{}\"", date)),
+        ("__TIME__", format!("\"{}\"", time)),
+    ]
+}
+
+/**
+ Seconds since the epoch the compilation is dated at, SOURCE_DATE_EPOCH overrides the clock for
+ reproducible builds as it does for gcc
+ */
+fn compilation_time() -> u64 {
+    std::env::var("SOURCE_DATE_EPOCH").ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()))
+}
+
+/**
+ `__DATE__` and `__TIME__` of a time in UTC: "Mmm dd yyyy" with the day padded by a space,
+ and "hh:mm:ss"
+ */
+fn date_and_time(seconds: u64) -> (String, String) {
+    let (days, seconds) = ((seconds / 86400) as i64, seconds % 86400);
+
+    // the civil date of a day number, days are counted in 400 year eras starting on March 1st
+    let shifted = days + 719468;
+    let era = shifted.div_euclid(146097);
+    let day_of_era = shifted.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = (month_from_march + 2) % 12;
+    let year = year_of_era + era * 400 + (month < 2) as i64;
+
+    (
+        format!("{} {:>2} {}", MONTHS[month as usize], day, year),
+        format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_and_time() {
+        assert_eq!(date_and_time(0), (String::from("Jan  1 1970"), String::from("00:00:00")));
+        assert_eq!(date_and_time(951782400), (String::from("Feb 29 2000"), String::from("00:00:00")));
+        assert_eq!(date_and_time(1700000000), (String::from("Nov 14 2023"), String::from("22:13:20")));
+    }
+}
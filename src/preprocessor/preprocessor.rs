@@ -0,0 +1,1146 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::common::errors::diagnostic::Diagnostic;
+use crate::common::errors::error::CompilerErrorKind;
+use crate::common::source_map::SourceMap;
+use crate::common::span::Span;
+use crate::common::Result;
+use crate::preprocessor::macros::{is_stringify, quote, stringify, Builtin, Macro};
+use crate::preprocessor::predefined::predefined_macros;
+use crate::parser::data_type::DataType;
+use crate::preprocessor::expression::evaluate;
+use crate::preprocessor::pp_token::{spell, tokenize, PpToken};
+use crate::scanner::token_stream::TokenStream;
+use crate::scanner::tokens::{Token, TokenType};
+
+/**
+ Directories searched for `#include <...>` after the ones given with -I
+ */
+static SYSTEM_INCLUDE_PATHS: [&str; 2] = ["/usr/local/include", "/usr/include"];
+
+const MAX_INCLUDE_DEPTH: usize = 200;
+
+/**
+ <p> The preprocessing stage between the scanner and the parser. </p>
+
+ Source files are scanned whole into tokens, the preprocessor then runs the directives and
+ expands macros as the parser asks for tokens. Tokens keep the span they were read at, the
+ tokens of a macro expansion the span of the invocation, so diagnostics point into the original
+ files.
+ */
+pub struct Preprocessor {
+
+    include_paths: Vec<PathBuf>,
+
+    /**
+    Every file read so far, tokens refer to them by index
+    */
+    source_map: SourceMap,
+    /**
+    Files being read, the innermost `#include` last
+    */
+    includes: Vec<SourceFile>,
+
+    macros: HashMap<String, Macro>,
+
+    /**
+    Files with `#pragma once`, they are not included again
+    */
+    once: HashSet<PathBuf>,
+
+    /**
+    Tokens to read again before the current file goes on, e.g. a macro expansion to rescan.
+    The next token is on top.
+    */
+    pending: Vec<PpToken>,
+    /**
+    Set while expanding a macro argument, the input ends with the argument
+    */
+    isolated: bool,
+
+    /**
+    Span of the last token read, the end of an isolated list is placed there
+    */
+    location: Span,
+
+    /**
+    Warnings so far, errors are returned instead
+    */
+    diagnostics: Vec<Diagnostic>,
+
+}
+
+struct SourceFile {
+    tokens: Vec<PpToken>,
+    index: usize,
+    /**
+    Index of the file in the source map, `#include "..."` looks next to it
+    */
+    file: usize,
+    /**
+    Conditional groups open in the file, the innermost last
+    */
+    conditionals: Vec<Conditional>,
+}
+
+impl SourceFile {
+
+    /**
+    Next token of the file, which ends with Eof: it is not consumed
+    */
+    fn next_token(&mut self) -> PpToken {
+        let token = self.tokens[self.index].clone();
+        if !token.is(TokenType::Eof) {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.tokens[self.index].line_start
+    }
+
+    /**
+    Whether the tokens read now are compiled, they are not in a skipped conditional group
+    */
+    fn is_active(&self) -> bool {
+        self.conditionals.last().is_none_or(|conditional| conditional.active)
+    }
+
+}
+
+/**
+ An `#if`, `#ifdef` or `#ifndef` group with its `#elif` and `#else` branches
+ */
+struct Conditional {
+    /**
+    The opening directive, reported if the group is not closed
+    */
+    start: PpToken,
+    /**
+    Whether the current branch is compiled
+    */
+    active: bool,
+    /**
+    Whether a branch was compiled already, or the enclosing group is skipped: later branches are
+    skipped then
+    */
+    taken: bool,
+    seen_else: bool,
+}
+
+impl Preprocessor {
+
+    pub fn new(include_paths: Vec<PathBuf>) -> Preprocessor {
+        let mut preprocessor = Self {
+            include_paths,
+            source_map: SourceMap::new(),
+            includes: vec!(),
+            macros: HashMap::new(),
+            once: HashSet::new(),
+            pending: vec!(),
+            isolated: false,
+            location: Span::default(),
+            diagnostics: vec!(),
+        };
+        for (name, replacement) in predefined_macros() {
+            let (end, line) = preprocessor.directive_text(format!("{} {}", name, replacement), "<built-in>");
+            preprocessor.define(&end, &line).expect("predefined macros are valid");
+        }
+        preprocessor.macros.insert(String::from("__FILE__"), Macro::builtin(Builtin::File));
+        preprocessor.macros.insert(String::from("__LINE__"), Macro::builtin(Builtin::Line));
+        preprocessor
+    }
+
+    /**
+    `-D name` or `-D name=definition` of the command line, a name alone is defined as 1
+    */
+    pub fn define_macro(&mut self, definition: &str) -> Result<()> {
+        let text = match definition.split_once('=') {
+            Some((name, replacement)) => format!("{} {}", name, replacement),
+            None => format!("{} 1", definition),
+        };
+        let (end, line) = self.directive_text(text, "<command line>");
+        self.define(&end, &line)
+    }
+
+    /**
+    `-U name` of the command line
+    */
+    pub fn undefine_macro(&mut self, name: &str) -> Result<()> {
+        let (end, line) = self.directive_text(name.to_string(), "<command line>");
+        self.undef(&end, &line)
+    }
+
+    /**
+    Tokens of a directive not read from a file, after the directive name: the end of the text
+    and the tokens
+    */
+    fn directive_text(&mut self, text: String, source: &str) -> (PpToken, Vec<PpToken>) {
+        let file = self.source_map.add_file(PathBuf::from(source), text.clone());
+        let mut tokens = tokenize(text, file, &mut self.diagnostics);
+        let end = tokens.pop().unwrap();
+        (end, tokens)
+    }
+
+    /**
+    Starts reading a file, its tokens come before the rest of the current file
+    */
+    pub fn push_file(&mut self, path: &Path) -> Result<()> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            CompilerErrorKind::FileError(format!("Cannot read '{}': {}", path.display(), e))
+        })?;
+        self.push_source(path, text)
+    }
+
+    pub fn push_source(&mut self, path: &Path, text: String) -> Result<()> {
+        let file = self.source_map.add_file(path.to_path_buf(), text.clone());
+        self.location = Span::new(file, 0, 0, 1, 1);
+        self.includes.push(SourceFile {
+            tokens: tokenize(text, file, &mut self.diagnostics),
+            index: 0,
+            file,
+            conditionals: vec!(),
+        });
+        Ok(())
+    }
+
+    /**
+    The files read so far, spans of the tokens refer to them
+    */
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /**
+    Hands over the warnings reported since the last call
+    */
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /**
+    Next token after preprocessing along with its spelling and layout, `scan` gives the token only
+    */
+    pub fn next_token(&mut self) -> Result<PpToken> {
+        let token = self.next_expanded()?;
+        self.location = token.span();
+        Ok(token)
+    }
+
+    fn current_file(&mut self) -> &mut SourceFile {
+        self.includes.last_mut().unwrap()
+    }
+
+    fn error(&self, at: &PpToken, message: String) -> CompilerErrorKind {
+        CompilerErrorKind::PreprocessorError(at.span(), message)
+    }
+
+    /**
+    Tokens after the operands of a directive are ignored, with a suggestion to remove them
+    */
+    fn extra_tokens(&mut self, tokens: &[PpToken], directive: &str) {
+        if let (Some(first), Some(last)) = (tokens.first(), tokens.last()) {
+            let span = first.span().to(last.span());
+            let message = format!("Extra tokens at end of #{} directive.", directive);
+            self.diagnostics.push(Diagnostic::warning(message).with_code("W0201").at(span).with_fix_it(span, String::new()));
+        }
+    }
+
+    /**
+    Next token before macro expansion, directives are run on the way
+    */
+    fn next_raw(&mut self) -> Result<PpToken> {
+        if let Some(token) = self.pending.pop() {
+            return Ok(token);
+        }
+        if self.isolated {
+            return Ok(PpToken::eof(self.location));
+        }
+        loop {
+            let source = self.current_file();
+            let token = source.next_token();
+            if token.is(TokenType::Eof) {
+                // reported once, the file ends after it
+                if let Some(conditional) = source.conditionals.pop() {
+                    source.conditionals.clear();
+                    return Err(self.error(&conditional.start, String::from("Unterminated conditional directive.")));
+                }
+                if self.includes.len() > 1 {
+                    self.includes.pop();
+                    continue;
+                }
+                return Ok(token);
+            }
+
+            if token.is(TokenType::Hash) && token.line_start {
+                let line = self.directive_line();
+                self.directive(&token, line)?;
+            } else if self.current_file().is_active() {
+                return Ok(token);
+            }
+        }
+    }
+
+    /**
+    Rest of the line of a directive in the current file
+    */
+    fn directive_line(&mut self) -> Vec<PpToken> {
+        let source = self.current_file();
+        let mut line = vec!();
+        while !source.at_line_start() {
+            line.push(source.next_token());
+        }
+        line
+    }
+
+    fn directive(&mut self, hash: &PpToken, line: Vec<PpToken>) -> Result<()> {
+        // a lone `#` is the null directive
+        let Some(name) = line.first() else {
+            return Ok(());
+        };
+        let rest = &line[1..];
+        // in skipped groups only the nesting of conditional directives matters
+        let conditional = matches!(name.name(), Some("if" | "ifdef" | "ifndef" | "elif" | "else" | "endif"));
+        if !conditional && !self.current_file().is_active() {
+            return Ok(());
+        }
+
+        match name.name() {
+            Some(kind @ ("if" | "ifdef" | "ifndef")) => {
+                let enclosing = self.current_file().is_active();
+                let condition = match kind {
+                    _ if !enclosing => Ok(false),
+                    "if" => self.condition(name, rest),
+                    _ => self.ifdef(name, rest).map(|defined| defined == (kind == "ifdef")),
+                };
+                // a condition in error still opens the group, skipped with the ones after it
+                let active = *condition.as_ref().unwrap_or(&false);
+                self.current_file().conditionals.push(Conditional {
+                    start: hash.clone(),
+                    active,
+                    taken: active || !enclosing || condition.is_err(),
+                    seen_else: false,
+                });
+                condition.map(|_| ())
+            },
+            Some("elif") => {
+                let taken = match self.current_file().conditionals.last() {
+                    None => return Err(self.error(name, String::from("#elif without #if."))),
+                    Some(conditional) if conditional.seen_else => {
+                        return Err(self.error(name, String::from("#elif after #else.")));
+                    },
+                    Some(conditional) => conditional.taken,
+                };
+                let condition = if taken { Ok(false) } else { self.condition(name, rest) };
+                let active = *condition.as_ref().unwrap_or(&false);
+                let conditional = self.current_file().conditionals.last_mut().unwrap();
+                conditional.active = active;
+                conditional.taken |= active || condition.is_err();
+                condition.map(|_| ())
+            },
+            Some("else") => {
+                match self.current_file().conditionals.last() {
+                    None => return Err(self.error(name, String::from("#else without #if."))),
+                    Some(conditional) if conditional.seen_else => {
+                        return Err(self.error(name, String::from("#else after #else.")));
+                    },
+                    Some(_) => {},
+                }
+                self.extra_tokens(rest, "else");
+                let conditional = self.current_file().conditionals.last_mut().unwrap();
+                conditional.active = !conditional.taken;
+                conditional.taken = true;
+                conditional.seen_else = true;
+                Ok(())
+            },
+            Some("endif") => {
+                if self.current_file().conditionals.pop().is_none() {
+                    return Err(self.error(name, String::from("#endif without #if.")));
+                }
+                self.extra_tokens(rest, "endif");
+                Ok(())
+            },
+            Some("include") => self.include(hash, rest.to_vec()),
+            Some("define") => self.define(hash, rest),
+            Some("undef") => self.undef(hash, rest),
+            Some("line") => self.line(hash, rest),
+            None if name.is(TokenType::Integer) => self.line_marker(hash, &line),
+            Some("error") => Err(self.error(hash, format!("#error {}", spell(rest)))),
+            Some("warning") => {
+                let message = format!("#warning {}", spell(rest));
+                self.diagnostics.push(Diagnostic::warning(message).with_code("W0202").at(hash.span()));
+                Ok(())
+            },
+            Some("pragma") => {
+                // other pragmas are ignored
+                if rest.first().and_then(PpToken::name) == Some("once") {
+                    let file = self.current_file().file;
+                    self.once.insert(canonical(self.source_map.path(file)));
+                }
+                Ok(())
+            },
+            _ => {
+                let message = format!("Invalid preprocessing directive '#{}'.", name.spelling);
+                Err(self.error(&line[0], message))
+            },
+        }
+    }
+
+    fn include(&mut self, hash: &PpToken, line: Vec<PpToken>) -> Result<()> {
+        // the header name may come from a macro
+        let line = match line.first() {
+            Some(token) if token.is(TokenType::String) || token.is(TokenType::HeaderName) => line,
+            _ => self.expand_list(line)?,
+        };
+
+        let (name, quoted) = match line.as_slice() {
+            [token] if token.is(TokenType::String) => {
+                (token.spelling[1..token.spelling.len() - 1].to_string(), true)
+            },
+            [token] if token.is(TokenType::HeaderName) => {
+                (String::from_utf8_lossy(token.token.get_bytes()).into_owned(), false)
+            },
+            // `<` ... `>` put together by macros
+            [less, name @ .., greater] if less.is(TokenType::Less) && greater.is(TokenType::Greater) => {
+                (spell(name), false)
+            },
+            _ => return Err(self.error(hash, String::from("#include expects \"FILENAME\" or <FILENAME>."))),
+        };
+
+        if self.includes.len() >= MAX_INCLUDE_DEPTH {
+            return Err(self.error(hash, String::from("#include nested too deeply.")));
+        }
+        let from = self.current_file().file;
+        let Some(path) = self.find_include(&name, quoted, from) else {
+            return Err(self.error(hash, format!("'{}' file not found.", name)));
+        };
+        if self.once.contains(&canonical(&path)) {
+            return Ok(());
+        }
+        self.push_file(&path)
+    }
+
+    /**
+    `#include "name"` looks next to the including file first, then both forms search the -I
+    directories and the system ones
+    */
+    fn find_include(&self, name: &str, quoted: bool, from: usize) -> Option<PathBuf> {
+        let current = self.source_map.path(from).parent().map(Path::to_path_buf).unwrap_or_default();
+        quoted.then_some(current)
+            .into_iter()
+            .chain(self.include_paths.iter().cloned())
+            .chain(SYSTEM_INCLUDE_PATHS.iter().map(PathBuf::from))
+            .map(|directory| directory.join(name))
+            .find(|path| path.is_file())
+    }
+
+    fn define(&mut self, hash: &PpToken, line: &[PpToken]) -> Result<()> {
+        let name = self.macro_name(hash, line)?;
+
+        let mut rest = 1;
+        // a parenthesis right after the name starts a parameter list
+        let params = match line.get(1) {
+            Some(paren) if paren.is(TokenType::LeftParen) && !paren.leading_space => {
+                let mut params: Vec<String> = vec!();
+                rest = 2;
+                if line.get(rest).is_some_and(|t| t.is(TokenType::RightParen)) {
+                    rest += 1;
+                } else {
+                    loop {
+                        let Some(param) = line.get(rest).and_then(PpToken::name) else {
+                            let at = line.get(rest).unwrap_or(paren);
+                            return Err(self.error(at, String::from("Expected a parameter name in macro parameter list.")));
+                        };
+                        if params.iter().any(|p| p == param) {
+                            let message = format!("Duplicate macro parameter '{}'.", param);
+                            return Err(self.error(&line[rest], message));
+                        }
+                        params.push(param.to_string());
+                        rest += 1;
+                        match line.get(rest) {
+                            Some(t) if t.is(TokenType::Comma) => rest += 1,
+                            Some(t) if t.is(TokenType::RightParen) => {
+                                rest += 1;
+                                break;
+                            },
+                            other => {
+                                let at = other.unwrap_or(&line[rest - 1]);
+                                return Err(self.error(at, String::from("Expected ',' or ')' in macro parameter list.")));
+                            },
+                        }
+                    }
+                }
+                Some(params)
+            },
+            _ => None,
+        };
+
+        let mut body = line[rest..].to_vec();
+        if let Some(first) = body.first_mut() {
+            first.leading_space = false;
+        }
+        let definition = Macro::new(params, body);
+
+        let body = &definition.body;
+        if let Some(at) = [body.first(), body.last()].into_iter().flatten().find(|t| t.is(TokenType::HashHash)) {
+            return Err(self.error(at, String::from("'##' cannot appear at either end of a macro expansion.")));
+        }
+        for (i, token) in body.iter().enumerate() {
+            if is_stringify(&definition, token) && body.get(i + 1).and_then(|t| definition.param_index(t)).is_none() {
+                return Err(self.error(token, String::from("'#' is not followed by a macro parameter.")));
+            }
+        }
+
+        if let Some(previous) = self.macros.get(&name) {
+            if !previous.same_definition(&definition) {
+                let message = format!("'{}' redefined differently.", name);
+                return Err(self.error(&line[0], message));
+            }
+        }
+        self.macros.insert(name, definition);
+        Ok(())
+    }
+
+    fn undef(&mut self, hash: &PpToken, line: &[PpToken]) -> Result<()> {
+        let name = self.macro_name(hash, line)?;
+        self.extra_tokens(&line[1..], "undef");
+        self.macros.remove(&name);
+        Ok(())
+    }
+
+    /**
+    Whether the macro of an `#ifdef` or `#ifndef` is defined
+    */
+    fn ifdef(&mut self, directive: &PpToken, line: &[PpToken]) -> Result<bool> {
+        let name = self.macro_name(directive, line)?;
+        self.extra_tokens(&line[1..], &directive.spelling);
+        Ok(self.macros.contains_key(&name))
+    }
+
+    /**
+    Value of the expression of an `#if` or `#elif`
+    */
+    fn condition(&mut self, directive: &PpToken, line: &[PpToken]) -> Result<bool> {
+        // `defined` is applied before macro expansion, which would replace its operand
+        let mut tokens = vec!();
+        let mut i = 0;
+        while i < line.len() {
+            let token = &line[i];
+            if token.name() != Some("defined") {
+                tokens.push(token.clone());
+                i += 1;
+                continue;
+            }
+            let parenthesized = line.get(i + 1).is_some_and(|t| t.is(TokenType::LeftParen));
+            let operand = i + 1 + parenthesized as usize;
+            let Some(name) = line.get(operand).and_then(PpToken::name) else {
+                return Err(self.error(token, String::from("Operator 'defined' requires an identifier.")));
+            };
+            if parenthesized && !line.get(operand + 1).is_some_and(|t| t.is(TokenType::RightParen)) {
+                return Err(self.error(token, String::from("Missing ')' after 'defined'.")));
+            }
+            let value = self.macros.contains_key(name) as u64;
+            tokens.push(PpToken {
+                token: Token::number_token(value, DataType::Int).with_span(token.span()),
+                spelling: value.to_string(),
+                ..token.clone()
+            });
+            i = operand + 1 + parenthesized as usize;
+        }
+
+        let tokens = self.expand_list(tokens)?;
+        evaluate(&tokens, directive)
+    }
+
+    /**
+    `#line number "name"`: the next line gets the number, and the file the name
+    */
+    fn line(&mut self, hash: &PpToken, line: &[PpToken]) -> Result<()> {
+        let tokens = self.expand_list(line.to_vec())?;
+        let directive_end = line.last().unwrap_or(hash).span().line;
+        self.set_line(hash, &tokens, directive_end)
+    }
+
+    /**
+    `# 12 "file" 1 3`: a line marker as written by `-E`, a `#line` whose flags after the file name
+    are ignored. It is not macro expanded.
+    */
+    fn line_marker(&mut self, hash: &PpToken, line: &[PpToken]) -> Result<()> {
+        if let Some(flag) = line.iter().skip(2).find(|flag| !matches!(flag.spelling.as_str(), "1" | "2" | "3" | "4")) {
+            return Err(self.error(flag, format!("Invalid flag '{}' in line marker.", flag.spelling)));
+        }
+        let directive_end = line.last().unwrap_or(hash).span().line;
+        self.set_line(hash, &line[..line.len().min(2)], directive_end)
+    }
+
+    /**
+    Numbers the line after the directive ending on `directive_end`, and names its file
+    */
+    fn set_line(&mut self, hash: &PpToken, tokens: &[PpToken], directive_end: usize) -> Result<()> {
+        let number = tokens.first()
+            .filter(|t| t.is(TokenType::Integer) && t.spelling.bytes().all(|c| c.is_ascii_digit()))
+            .and_then(|t| t.spelling.parse::<usize>().ok())
+            .filter(|number| (1..=2147483647).contains(number));
+        let Some(number) = number else {
+            let at = tokens.first().unwrap_or(hash).clone();
+            return Err(self.error(&at, String::from("#line directive requires a positive integer argument.")));
+        };
+        let name = match &tokens[1..] {
+            [] => None,
+            [name] if name.is(TokenType::String) => Some(String::from_utf8_lossy(name.token.get_bytes()).into_owned()),
+            [other, ..] => {
+                let other = other.clone();
+                return Err(self.error(&other, String::from("Invalid filename in #line directive.")));
+            },
+        };
+
+        let presumed_file = name.map(|name| self.source_map.add_file(PathBuf::from(name), String::new()));
+        let file = self.current_file().file;
+        self.source_map.add_line_mark(file, directive_end + 1, number, presumed_file);
+        Ok(())
+    }
+
+    /**
+    Name of the macro a `#define` or `#undef` is about
+    */
+    fn macro_name(&mut self, hash: &PpToken, line: &[PpToken]) -> Result<String> {
+        let Some(token) = line.first() else {
+            return Err(self.error(hash, String::from("Macro name missing.")));
+        };
+        match token.name() {
+            Some("defined") => Err(self.error(token, String::from("'defined' cannot be used as a macro name."))),
+            Some(name) => Ok(name.to_string()),
+            None => Err(self.error(token, String::from("Macro names must be identifiers."))),
+        }
+    }
+
+    /**
+    Next token after macro expansion
+    */
+    fn next_expanded(&mut self) -> Result<PpToken> {
+        loop {
+            let token = self.next_raw()?;
+            let Some(name) = token.name() else {
+                return Ok(token);
+            };
+            if token.hide_set.contains(name) {
+                return Ok(token);
+            }
+            let Some(definition) = self.macros.get(name).cloned() else {
+                return Ok(token);
+            };
+            if let Some(builtin) = definition.builtin {
+                return Ok(self.expand_builtin(builtin, token));
+            }
+            let name = name.to_string();
+
+            let expansion = match &definition.params {
+                None => {
+                    let mut hide_set = token.hide_set.clone();
+                    hide_set.insert(name);
+                    self.substitute(&definition, &[], hide_set, &token)?
+                },
+                Some(params) => {
+                    // a function-like macro name is only expanded when followed by arguments
+                    let next = self.next_raw()?;
+                    if !next.is(TokenType::LeftParen) {
+                        self.pending.push(next);
+                        return Ok(token);
+                    }
+                    let (arguments, close) = self.arguments(&token, &name, params.len())?;
+                    let mut hide_set: HashSet<String> = token.hide_set.intersection(&close.hide_set).cloned().collect();
+                    hide_set.insert(name);
+                    self.substitute(&definition, &arguments, hide_set, &token)?
+                },
+            };
+            self.pending.extend(expansion.into_iter().rev());
+        }
+    }
+
+    /**
+    `__FILE__` or `__LINE__` where the token is, `#line` included
+    */
+    fn expand_builtin(&self, builtin: Builtin, token: PpToken) -> PpToken {
+        let (value, spelling) = match builtin {
+            Builtin::File => {
+                let name = self.source_map.presumed_path(token.span()).display().to_string();
+                (Token::string_token(name.clone().into_bytes()), quote(&name))
+            },
+            Builtin::Line => {
+                let (line, _) = self.source_map.line_column(token.span());
+                (Token::number_token(line as u64, DataType::Int), line.to_string())
+            },
+        };
+        PpToken {
+            token: value.with_span(token.span()),
+            spelling,
+            ..token
+        }
+    }
+
+    /**
+    Arguments of a function-like macro invocation up to the closing parenthesis, which is
+    returned too
+    */
+    fn arguments(&mut self, invocation: &PpToken, name: &str, count: usize) -> Result<(Vec<Vec<PpToken>>, PpToken)> {
+        let mut arguments: Vec<Vec<PpToken>> = vec![vec!()];
+        let mut depth = 0;
+        let close = loop {
+            let token = self.next_raw()?;
+            if token.is(TokenType::Eof) {
+                let message = format!("Unterminated argument list invoking macro '{}'.", name);
+                return Err(self.error(invocation, message));
+            } else if token.is(TokenType::LeftParen) {
+                depth += 1;
+            } else if token.is(TokenType::RightParen) {
+                if depth == 0 {
+                    break token;
+                }
+                depth -= 1;
+            } else if token.is(TokenType::Comma) && depth == 0 {
+                arguments.push(vec!());
+                continue;
+            }
+            arguments.last_mut().unwrap().push(token);
+        };
+
+        // `f()` passes no argument to a macro without parameters
+        if count == 0 && arguments.len() == 1 && arguments[0].is_empty() {
+            arguments.clear();
+        }
+        if arguments.len() != count {
+            let message = format!("Macro '{}' requires {} argument(s), but {} given.", name, count, arguments.len());
+            return Err(self.error(invocation, message));
+        }
+        Ok((arguments, close))
+    }
+
+    /**
+    The replacement of a macro invocation: parameters in the body are replaced by the arguments,
+    `#` and `##` applied, and the hide set added to every token
+    */
+    fn substitute(&mut self, definition: &Macro, arguments: &[Vec<PpToken>], hide_set: HashSet<String>, invocation: &PpToken) -> Result<Vec<PpToken>> {
+        let body = &definition.body;
+        let from_body = |token: &PpToken| {
+            let mut token = token.clone();
+            token.relocate(invocation);
+            token
+        };
+
+        let mut output: Vec<PpToken> = vec!();
+        let mut i = 0;
+        while i < body.len() {
+            let token = &body[i];
+            let next = body.get(i + 1);
+
+            if is_stringify(definition, token) {
+                let index = definition.param_index(next.unwrap()).unwrap();
+                output.push(stringify(&arguments[index], &from_body(token)));
+                i += 2;
+                continue;
+            }
+
+            if token.is(TokenType::HashHash) {
+                let right = &body[i + 1];
+                let operand = match definition.param_index(right) {
+                    Some(index) => arguments[index].clone(),
+                    None => vec![from_body(right)],
+                };
+                self.paste(&mut output, operand)?;
+                i += 2;
+                continue;
+            }
+
+            if let Some(index) = definition.param_index(token) {
+                // operands of `##` are not expanded
+                if next.is_some_and(|t| t.is(TokenType::HashHash)) {
+                    if arguments[index].is_empty() {
+                        match body.get(i + 2).and_then(|t| definition.param_index(t)) {
+                            Some(right) => {
+                                output.extend(arguments[right].iter().cloned());
+                                i += 3;
+                            },
+                            None => i += 2,
+                        }
+                    } else {
+                        output.extend(arguments[index].iter().cloned());
+                        i += 1;
+                    }
+                    continue;
+                }
+                let expanded = self.expand_list(arguments[index].clone())?;
+                output.extend(expanded);
+                i += 1;
+                continue;
+            }
+
+            output.push(from_body(token));
+            i += 1;
+        }
+
+        for (i, token) in output.iter_mut().enumerate() {
+            token.hide_set.extend(hide_set.iter().cloned());
+            token.line_start = false;
+            if i == 0 {
+                token.leading_space = invocation.leading_space;
+            }
+        }
+        Ok(output)
+    }
+
+    /**
+    The `##` operator: the last token so far and the first of the right operand form a new token
+    */
+    fn paste(&mut self, output: &mut Vec<PpToken>, right: Vec<PpToken>) -> Result<()> {
+        let mut right = right.into_iter();
+        let Some(first) = right.next() else {
+            return Ok(());
+        };
+        let Some(left) = output.pop() else {
+            output.push(first);
+            output.extend(right);
+            return Ok(());
+        };
+
+        let text = format!("{}{}", left.spelling, first.spelling);
+        // a pasted token is too short for the scanner to warn about
+        let pasted = match tokenize(text, left.span().file, &mut vec!()).as_slice() {
+            [token, _] if !token.is(TokenType::Invalid) => token.clone(),
+            _ => {
+                let message = format!("Pasting \"{}\" and \"{}\" does not give a valid preprocessing token.", left.spelling, first.spelling);
+                return Err(self.error(&left, message));
+            },
+        };
+        output.push(PpToken {
+            token: pasted.token.with_span(left.span().to(first.span())),
+            spelling: pasted.spelling,
+            hide_set: left.hide_set.intersection(&first.hide_set).cloned().collect(),
+            ..left
+        });
+        output.extend(right);
+        Ok(())
+    }
+
+    /**
+    Fully macro-expands a list of tokens on its own, e.g. a macro argument
+    */
+    fn expand_list(&mut self, tokens: Vec<PpToken>) -> Result<Vec<PpToken>> {
+        let pending = std::mem::replace(&mut self.pending, tokens.into_iter().rev().collect());
+        let isolated = std::mem::replace(&mut self.isolated, true);
+
+        let mut expanded = vec!();
+        let result = loop {
+            match self.next_expanded() {
+                Ok(token) if token.is(TokenType::Eof) => break Ok(expanded),
+                Ok(token) => expanded.push(token),
+                Err(error) => break Err(error),
+            }
+        };
+
+        self.pending = pending;
+        self.isolated = isolated;
+        result
+    }
+
+}
+
+/**
+ The same file is named in many ways, e.g. through links or `..`
+ */
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+impl TokenStream for Preprocessor {
+
+    fn scan(&mut self) -> Result<Token> {
+        let token = self.next_token()?;
+        if token.is(TokenType::Invalid) {
+            let message = String::from_utf8_lossy(token.token.get_bytes()).into_owned();
+            return Err(CompilerErrorKind::ScannerError(token.span(), message));
+        }
+        Ok(token.token)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preprocess(text: &str) -> Result<String> {
+        let mut preprocessor = Preprocessor::new(vec!());
+        preprocessor.push_source(Path::new("test.c"), text.to_string())?;
+        let mut output = String::new();
+        loop {
+            let token = preprocessor.next_expanded()?;
+            if token.is(TokenType::Eof) {
+                return Ok(output);
+            }
+            output.push_str(&token.spelling);
+        }
+    }
+
+    fn strip(text: &str) -> String {
+        text.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+
+    #[test]
+    fn test_macro_expansion() {
+        // the example of C11 6.10.3.5, with the tokens this scanner knows
+        let text = "
+            #define x 3
+            #define f(a) f(x * (a))
+            #undef x
+            #define x 2
+            #define g f
+            #define z z+0
+            #define h g(~
+            #define m(a) a(w)
+            #define w 0,1
+            #define t(a) a
+            #define q(x) x
+            #define r(x,y) x ## y
+            f(y+1) + f(f(z)) % t(t(g)(0) + t)(1);
+            g(x+(3,4)-w) | h 5) & m
+                (f)^m(m);
+            q(1) r(2,3) r(4,) r(,5) r(,)
+        ";
+        assert_eq!(
+            preprocess(text).unwrap(),
+            strip("
+                f(2 * (y+1)) + f(2 * (f(2 * (z+0)))) % f(2 * (0)) + t(1);
+                f(2 * (2+(3,4)-0,1)) | f(2 * (~ 5)) & f(2 * (0,1))^m(0,1);
+                1 23 4 5
+            ")
+        );
+    }
+
+    #[test]
+    fn test_stringification() {
+        let mut preprocessor = Preprocessor::new(vec!());
+        let text = "#define str(s) # s\n#define xstr(s) str(s)\n#define foo 4\nstr(  a  +\n \"\\n\" ) xstr(foo)";
+        preprocessor.push_source(Path::new("test.c"), text.to_string()).unwrap();
+        assert_eq!(preprocessor.scan().unwrap().get_bytes(), b"a + \"\\n\"");
+        assert_eq!(preprocessor.scan().unwrap().get_bytes(), b"4");
+    }
+
+    #[test]
+    fn test_directive_errors() {
+        assert!(matches!(preprocess("#define f(x, x) x"), Err(CompilerErrorKind::PreprocessorError(Span { line: 1, column: 14, .. }, _))));
+        assert!(matches!(preprocess("#define f(x) #y"), Err(CompilerErrorKind::PreprocessorError(Span { line: 1, column: 14, .. }, _))));
+        assert!(matches!(preprocess("#define a 1\n#define a 2"), Err(CompilerErrorKind::PreprocessorError(Span { line: 2, column: 9, .. }, _))));
+        assert!(matches!(preprocess("#define f(x) x\nf(1,\n2)"), Err(CompilerErrorKind::PreprocessorError(Span { line: 2, column: 1, .. }, _))));
+        assert!(matches!(preprocess("#define p(a, b) a ## b\np(+, /)"), Err(CompilerErrorKind::PreprocessorError(Span { line: 2, column: 3, .. }, _))));
+        assert!(matches!(preprocess("x\n  #include \"missing.h\""), Err(CompilerErrorKind::PreprocessorError(Span { line: 2, column: 3, .. }, _))));
+        assert_eq!(preprocess("#define a 1 /* same */\n#define a 1\n#\na").unwrap(), "1");
+    }
+
+    #[test]
+    fn test_conditionals() {
+        let text = "
+            #define ONE 1
+            #define EMPTY
+            #if ONE + 1 == 2 && defined(EMPTY) && !defined ZERO
+            a
+            #  if 0
+            #    if 1 / 0
+            #    endif
+            it's skipped
+            #    error not reached
+            #  elif -1 < 0u
+            b
+            #    undef ONE
+            #  elif ONE
+            c
+            #  else
+            d
+            #  endif
+            #elif 1
+            e
+            #endif
+            #ifdef ONE
+            f
+            #endif
+            #ifndef ONE
+            g
+            #else
+            h
+            #endif
+            #if (2 || 1 / 0) && ~0 == -1 && (7 >> 1) * 4 % 5 == 2 && 'A' == 65 && UNDEFINED == 0
+            i
+            #endif
+            #if (0 - 1) / 2 == 0 && -1 >> 63 == -1 && 0xffffffffffffffff == -1 && 1 << 63 < 0
+            j
+            #endif
+            #if ONE && (1 ? 2 : 0) && (0 ? 1 / 0 : 1 ? 3 : 1 % 0) == 3 && (1 ? -1 : 0u) > 0 && !(0 ? 1 : 0)
+            k
+            #endif
+        ";
+        assert_eq!(preprocess(text).unwrap(), "acfhijk");
+    }
+
+    #[test]
+    fn test_conditional_errors() {
+        let error = |text: &str| match preprocess(text) {
+            Err(CompilerErrorKind::PreprocessorError(span, message)) => (span.line, span.column, message),
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(error("#if 1\n#else\n#else\n#endif"), (3, 2, String::from("#else after #else.")));
+        assert_eq!(error("#endif"), (1, 2, String::from("#endif without #if.")));
+        assert_eq!(error("#if 0\n#elif 1\n#else\n#elif 1\n#endif"), (4, 2, String::from("#elif after #else.")));
+        assert_eq!(error("x\n  #ifdef X\n#if 1\n#endif"), (2, 3, String::from("Unterminated conditional directive.")));
+        assert_eq!(error("#if 1 +\n#endif"), (1, 7, String::from("Expected value in expression.")));
+        assert_eq!(error("#if (1 2)\n#endif"), (1, 8, String::from("Missing ')' in expression.")));
+        assert_eq!(error("#if 1 % (2 - 2)\n#endif"), (1, 7, String::from("Division by zero in preprocessor expression.")));
+        assert_eq!(error("#if 1 ? 2\n#endif"), (1, 9, String::from("Missing ':' in expression.")));
+        assert_eq!(error("#if 0 ? 1 : 1 / 0\n#endif"), (1, 15, String::from("Division by zero in preprocessor expression.")));
+        assert_eq!(error("#if defined(X\n#endif"), (1, 5, String::from("Missing ')' after 'defined'.")));
+        assert_eq!(error("#if\n#endif"), (1, 2, String::from("#if with no expression.")));
+        assert_eq!(error("#define WHY \"no\"\n#error WHY   not\n"), (2, 1, String::from("#error WHY not")));
+        assert!(preprocess("#warning just a warning\n#pragma unknown\n").is_ok());
+    }
+
+    #[test]
+    fn test_condition_errors_skip_group() {
+        // every error is collected, the groups whose condition failed are skipped
+        let preprocess = |text: &str| {
+            let mut preprocessor = Preprocessor::new(vec!());
+            preprocessor.push_source(Path::new("test.c"), text.to_string()).unwrap();
+            let (mut output, mut errors) = (String::new(), vec!());
+            loop {
+                match preprocessor.next_expanded() {
+                    Ok(token) if token.is(TokenType::Eof) => return (output, errors),
+                    Ok(token) => output.push_str(&token.spelling),
+                    Err(CompilerErrorKind::PreprocessorError(span, _)) => errors.push(span.line),
+                    Err(e) => panic!("{:?}", e),
+                }
+            }
+        };
+        assert_eq!(preprocess("#if 1/0
+a
+#else
+b
+#endif
+c"), (String::from("c"), vec!(1)));
+        assert_eq!(preprocess("#if
+a
+#elif 1
+b
+#endif"), (String::new(), vec!(1)));
+        assert_eq!(preprocess("#ifdef
+a
+#else
+b
+#endif"), (String::new(), vec!(1)));
+        assert_eq!(preprocess("#define F(x) x
+#if F(
+a
+#endif
+b"), (String::from("b"), vec!(2)));
+        assert_eq!(preprocess("#if 0
+a
+#elif 1/0
+b
+#else
+#error not reached
+#endif"), (String::new(), vec!(3)));
+        assert_eq!(preprocess("#if 1
+a
+#elif 1/0
+b
+#endif"), (String::from("a"), vec!()));
+    }
+
+    #[test]
+    fn test_line_directive() {
+        let mut preprocessor = Preprocessor::new(vec!());
+        let text = "a\n#line 100\n\nb\n#define FILE \"other.c\"\n#line 7 FILE\nc";
+        preprocessor.push_source(Path::new("test.c"), text.to_string()).unwrap();
+        let positions: Vec<_> = (0..3).map(|_| {
+            let span = preprocessor.next_expanded().unwrap().span();
+            (preprocessor.source_map.presumed_path(span).to_path_buf(), preprocessor.source_map.line_column(span))
+        }).collect();
+        assert_eq!(positions, vec![
+            (PathBuf::from("test.c"), (1, 1)),
+            (PathBuf::from("test.c"), (101, 1)),
+            (PathBuf::from("other.c"), (7, 1)),
+        ]);
+        assert!(matches!(preprocess("#line 0"), Err(CompilerErrorKind::PreprocessorError(Span { line: 1, column: 7, .. }, _))));
+
+        // line markers as `-E` writes them
+        let mut preprocessor = Preprocessor::new(vec!());
+        let text = "a\n# 12 \"inc.h\" 1 3\nb\n# 3 \"test.c\" 2\nc";
+        preprocessor.push_source(Path::new("test.c"), text.to_string()).unwrap();
+        let positions: Vec<_> = (0..3).map(|_| {
+            let span = preprocessor.next_expanded().unwrap().span();
+            (preprocessor.source_map.presumed_path(span).to_path_buf(), preprocessor.source_map.line_column(span))
+        }).collect();
+        assert_eq!(positions, vec![
+            (PathBuf::from("test.c"), (1, 1)),
+            (PathBuf::from("inc.h"), (12, 1)),
+            (PathBuf::from("test.c"), (3, 1)),
+        ]);
+        assert!(matches!(preprocess("# 1 \"a.c\" 5"), Err(CompilerErrorKind::PreprocessorError(Span { line: 1, column: 11, .. }, _))));
+    }
+
+    #[test]
+    fn test_predefined_macros() {
+        let mut preprocessor = Preprocessor::new(vec!());
+        preprocessor.define_macro("DEBUG").unwrap();
+        preprocessor.define_macro("LEVEL=2+1").unwrap();
+        preprocessor.define_macro("SQUARE(x)=((x)*(x))").unwrap();
+        preprocessor.undefine_macro("__linux__").unwrap();
+        assert!(preprocessor.define_macro("1X").is_err());
+
+        let text = "#if __STDC__ && __STDC_VERSION__ >= 201112L && defined(__x86_64__) && DEBUG && !defined __linux__
+            #define WHERE __FILE__ __LINE__
+            WHERE
+            #line 20 \"renamed.c\"
+            LEVEL SQUARE(__LINE__) WHERE
+            #endif";
+        preprocessor.push_source(Path::new("dir/test.c"), text.to_string()).unwrap();
+        let mut spelling = String::new();
+        loop {
+            let token = preprocessor.next_expanded().unwrap();
+            if token.is(TokenType::Eof) {
+                break;
+            }
+            spelling.push_str(&token.spelling);
+            spelling.push(' ');
+        }
+        assert_eq!(spelling, "\"dir/test.c\" 3 2 + 1 ( ( 20 ) * ( 20 ) ) \"renamed.c\" 20 ");
+
+        assert!(preprocess("__DATE__ __TIME__").unwrap().starts_with('"'));
+    }
+
+    #[test]
+    fn test_include() {
+        let directory = std::env::temp_dir().join(format!("pp_include_{}", std::process::id()));
+        fs::create_dir_all(directory.join("sys")).unwrap();
+        fs::write(directory.join("sys/local.h"), "#define TWICE(x) ((x) * 2)\nint local;\n").unwrap();
+        fs::write(directory.join("sys/lib.h"), "#pragma once\n#include \"local.h\"\nint lib; oops\n").unwrap();
+
+        let mut preprocessor = Preprocessor::new(vec![directory.join("sys")]);
+        let text = "#include <lib.h>\n#include \"sys/lib.h\"\nTWICE(3) oops";
+        preprocessor.push_source(&directory.join("main.c"), text.to_string()).unwrap();
+        let mut tokens = vec!();
+        loop {
+            let token = preprocessor.next_expanded().unwrap();
+            if token.is(TokenType::Eof) {
+                break;
+            }
+            let span = token.span();
+            tokens.push((token.spelling.clone(), preprocessor.source_map.path(span.file).to_path_buf(), (span.line, span.column)));
+        }
+        fs::remove_dir_all(&directory).unwrap();
+
+        let spelling: String = tokens.iter().map(|(s, _, _)| s.as_str()).collect();
+        assert_eq!(spelling, "intlocal;intlib;oops((3)*2)oops");
+        // tokens point into the files they were read from
+        assert_eq!(tokens[6], (String::from("oops"), directory.join("sys/lib.h"), (3, 10)));
+        assert_eq!(tokens[tokens.len() - 1], (String::from("oops"), directory.join("main.c"), (3, 10)));
+    }
+}
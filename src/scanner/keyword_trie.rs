@@ -86,10 +86,16 @@ impl KeywordTrie {
         keyword_trie.insert("else", TokenType::Else);
         keyword_trie.insert("for", TokenType::For);
         keyword_trie.insert("while", TokenType::While);
+        keyword_trie.insert("do", TokenType::Do);
         keyword_trie.insert("return", TokenType::Return);
 
+        keyword_trie.insert("void", TokenType::KeyVoid);
+        keyword_trie.insert("char", TokenType::KeyChar);
+        keyword_trie.insert("short", TokenType::KeyShort);
         keyword_trie.insert("int", TokenType::KeyInt);
         keyword_trie.insert("long", TokenType::KeyLong);
+        keyword_trie.insert("signed", TokenType::KeySigned);
+        keyword_trie.insert("unsigned", TokenType::KeyUnsigned);
         keyword_trie.insert("float", TokenType::KeyFloat);
         keyword_trie.insert("double", TokenType::KeyDouble);
         keyword_trie.insert("string", TokenType::KeyString);
@@ -99,7 +105,7 @@ impl KeywordTrie {
         keyword_trie
     }
 
-    pub fn into_checker(&self) -> KeywordTrieChecker {
+    pub fn into_checker(&self) -> KeywordTrieChecker<'_> {
         KeywordTrieChecker {
             trie: self,
             curr: Some(&self.root),
@@ -1,4 +1,5 @@
 pub mod tokens;
 pub mod scanner;
+pub mod token_stream;
 mod number_parser;
-pub mod keyword_trie;
\ No newline at end of file
+pub mod keyword_trie;
@@ -1,4 +1,5 @@
-use crate::common::errors::error::CompilerErrorKind;
+use crate::parser::data_type::DataType;
+use crate::scanner::tokens::{Token, TokenType};
 
 pub struct NumberParser {}
 
@@ -14,6 +15,13 @@ impl NumberParser {
 
 }
 
+/**
+ <p> Validates a numeric constant once the scanner has collected all of its characters. </p>
+
+ The characters are collected like a preprocessing number: digits, letters, `_`, `.` and the sign of
+ an exponent, so that malformed constants such as `1_000` or `09` are rejected as a whole instead of
+ being split into several tokens.
+ */
 pub struct NumberChecker {
 
     cache: String,
@@ -27,22 +35,178 @@ impl NumberChecker {
     }
 
     pub fn update(&mut self, c: char) {
-        // self.current = self.current * 10 + c as i64 - '0' as i64;
         self.cache.push(c);
     }
 
-    pub fn check(&mut self) -> Result<i64, CompilerErrorKind> {
-        // TODO: validate numbers, and support multiple
-        // let return_value = Ok(Token::number_token(self.current));
-        // self.current = 0;
-        // return_value
-        let return_value = Ok(self.cache.parse::<i64>().unwrap());
+    /**
+    The token of the collected constant, or the message of why it is invalid
+    */
+    pub fn check(&mut self) -> Result<Token, String> {
+        let return_value = if self.is_floating() {
+            self.check_floating()
+        } else {
+            self.check_integer()
+        };
         self.cache.clear();
         return_value
     }
 
+    /**
+    Decimal floating constants, e.g. `1.5`, `.5`, `1e-3` or `2.f`
+    */
+    fn is_floating(&self) -> bool {
+        !self.is_hex() && self.cache.contains(['.', 'e', 'E'])
+    }
+
+    fn is_hex(&self) -> bool {
+        self.cache.starts_with("0x") || self.cache.starts_with("0X")
+    }
+
+    fn check_floating(&self) -> Result<Token, String> {
+        let (digits, token_type) = match self.cache.strip_suffix(['f', 'F']) {
+            Some(digits) => (digits, TokenType::Float),
+            None => (self.cache.as_str(), TokenType::Double),
+        };
+        if digits.ends_with(['l', 'L']) {
+            return Err(format!("Long double constant '{}' is not supported.", self.cache));
+        }
+        // Rust also accepts forms C does not have, e.g. `inf`, but those never start with a digit
+        let invalid = || format!("Invalid floating constant '{}'.", self.cache);
+
+        let value = match token_type {
+            // rounded to float directly, rounding through double could differ
+            TokenType::Float => digits.parse::<f32>().map_err(|_| invalid())? as f64,
+            _ => digits.parse::<f64>().map_err(|_| invalid())?,
+        };
+        if value.is_infinite() {
+            return Err(format!(
+                "Floating constant '{}' is out of range for {:?}.",
+                self.cache,
+                if token_type == TokenType::Float { DataType::Float } else { DataType::Double }
+            ));
+        }
+        Ok(Token::float_token(token_type, value))
+    }
+
+    fn check_integer(&self) -> Result<Token, String> {
+        let text = self.cache.as_str();
+        let (radix, prefix_length, name) = if self.is_hex() {
+            (16, 2, "hexadecimal")
+        } else if text.starts_with("0b") || text.starts_with("0B") {
+            (2, 2, "binary")
+        } else if text.starts_with('0') {
+            // the leading 0 doubles as the only digit of `0`
+            (8, 1, "octal")
+        } else {
+            (10, 0, "decimal")
+        };
+
+        let rest = &text[prefix_length..];
+        let digits_length = rest
+            .find(|c: char| if radix == 16 { !c.is_ascii_hexdigit() } else { !c.is_ascii_digit() })
+            .unwrap_or(rest.len());
+        let (digits, suffix) = rest.split_at(digits_length);
+
+        if let Some(digit) = digits.chars().find(|c| !c.is_digit(radix)) {
+            return Err(format!("Invalid digit '{}' in {} constant.", digit, name));
+        }
+        if digits.is_empty() && radix != 8 {
+            return Err(format!("Missing digits in {} constant '{}'.", name, text));
+        }
+        let Some((unsigned, long)) = Self::parse_suffix(suffix) else {
+            return Err(format!("Invalid suffix '{}' on integer constant.", suffix));
+        };
+
+        let too_large = || format!("Integer constant '{}' is too large for its type.", text);
+        let value = if digits.is_empty() {
+            0
+        } else {
+            u64::from_str_radix(digits, radix).map_err(|_| too_large())?
+        };
+
+        // the first of the candidate types that can represent the value, decimal constants
+        // without a `u` suffix only ever get a signed type
+        let candidates: &[DataType] = match (unsigned, long, radix == 10) {
+            (false, 0, true) => &[DataType::Int, DataType::Long],
+            (false, 0, false) => &[DataType::Int, DataType::UnsignedInt, DataType::Long, DataType::UnsignedLong],
+            (true, 0, _) => &[DataType::UnsignedInt, DataType::UnsignedLong],
+            (false, 1, true) => &[DataType::Long],
+            (false, 1, false) => &[DataType::Long, DataType::UnsignedLong],
+            (true, 1, _) => &[DataType::UnsignedLong],
+            (false, _, true) => &[DataType::LongLong],
+            (false, _, false) => &[DataType::LongLong, DataType::UnsignedLongLong],
+            (true, _, _) => &[DataType::UnsignedLongLong],
+        };
+        match candidates.iter().find(|data_type| value <= data_type.max_value()) {
+            Some(data_type) => Ok(Token::number_token(value, *data_type)),
+            None => Err(too_large()),
+        }
+    }
+
+    /**
+    Whether an integer suffix is unsigned and how many `l`s it has, None if it is not one of
+    `u`, `l`, `ll` and their combinations in any case, `ll` has to be of a single case
+    */
+    fn parse_suffix(suffix: &str) -> Option<(bool, usize)> {
+        let (unsigned, long) = match suffix.strip_prefix(['u', 'U']) {
+            Some(long) => (true, long),
+            None => match suffix.strip_suffix(['u', 'U']) {
+                Some(long) => (true, long),
+                None => (false, suffix),
+            },
+        };
+        match long {
+            "" => Some((unsigned, 0)),
+            "l" | "L" => Some((unsigned, 1)),
+            "ll" | "LL" => Some((unsigned, 2)),
+            _ => None,
+        }
+    }
+
     pub fn can_consume(&self, c: &char) -> bool {
+        // the sign of an exponent, as in `1e-3`
+        if *c == '+' || *c == '-' {
+            return !self.is_hex() && self.cache.ends_with(['e', 'E']);
+        }
         c.is_ascii_alphanumeric() || *c == '_' || *c == '.'
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(text: &str) -> Result<Token, String> {
+        let mut checker = NumberChecker::new();
+        text.chars().for_each(|c| checker.update(c));
+        checker.check()
+    }
+
+    fn integer(text: &str) -> (u64, DataType) {
+        let token = check(text).unwrap();
+        (token.get_value(), token.get_data_type())
+    }
+
+    #[test]
+    fn test_integer_constants() {
+        assert_eq!(integer("0"), (0, DataType::Int));
+        assert_eq!(integer("0x1F"), (31, DataType::Int));
+        assert_eq!(integer("017"), (15, DataType::Int));
+        assert_eq!(integer("0b101"), (5, DataType::Int));
+        assert_eq!(integer("10u"), (10, DataType::UnsignedInt));
+        assert_eq!(integer("10lu"), (10, DataType::UnsignedLong));
+        assert_eq!(integer("10ULL"), (10, DataType::UnsignedLongLong));
+        assert_eq!(integer("2147483648"), (2147483648, DataType::Long));
+        assert_eq!(integer("0x80000000"), (2147483648, DataType::UnsignedInt));
+        assert_eq!(integer("0xFFFFFFFFFFFFFFFF"), (u64::MAX, DataType::UnsignedLong));
+    }
+
+    #[test]
+    fn test_invalid_constants() {
+        for text in ["1_000", "09", "0b2", "0x", "10lul", "1lL", "99999999999999999999", "9223372036854775808", "1e", "1.5L"] {
+            assert!(check(text).is_err(), "{}", text);
+        }
+        assert!(check("1e999").unwrap_err().contains("out of range"));
+    }
+}
@@ -1,8 +1,11 @@
+use crate::common::errors::diagnostic::Diagnostic;
 use crate::common::errors::error::CompilerErrorKind;
+use crate::common::span::Span;
 use crate::common::Result;
 use crate::scanner::number_parser::{NumberParser};
 use crate::scanner::tokens::{Token, TokenType};
 use crate::scanner::keyword_trie::KeywordTrie;
+use crate::scanner::token_stream::TokenStream;
 use std::iter::Peekable;
 use std::str::Chars;
 use crate::source_code::SourceCode;
@@ -11,83 +14,251 @@ pub struct Scanner<'a> {
     keyword_trie: KeywordTrie,
     number_parser: NumberParser,
     chars: ScannerPeekable<'a>,
+    text: &'a str,
+
+    /**
+    Position and byte offset of the first character of the last token
+    */
+    token_start: (usize, usize),
+    token_offset: usize,
+    /**
+    Whether a new line or whitespace was seen since the last token, comments count as whitespace
+    */
+    line_break: bool,
+    space: bool,
+    /**
+    Layout of the last token: first on its line, preceded by whitespace
+    */
+    line_start: bool,
+    leading_space: bool,
+    /**
+    Whether the last tokens are `#` `include` at the start of a line, a header name may follow
+    */
+    directive_hash: bool,
+    header_name: bool,
+
+    /**
+    Warnings about the text, e.g. of comments looking nested
+    */
+    diagnostics: Vec<Diagnostic>,
 }
 
 struct ScannerPeekable<'a> {
     chars: Peekable<Chars<'a>>,
+    /**
+    Index of the scanned file in the source map, tokens are placed in it
+    */
+    file: usize,
 
     line_number: usize,
     col_number: usize,
+    /**
+    Byte offset of the next character in the source text, and right after the last one read:
+    they differ by the line splices looked past
+    */
+    offset: usize,
+    start: usize,
+    end: usize,
 }
 
 impl<'a> ScannerPeekable<'a> {
     fn new(chars: Peekable<Chars<'a>>) -> Self {
         Self {
             chars,
+            file: 0,
             line_number: 1,
             col_number: 0,
+            offset: 0,
+            start: 0,
+            end: 0,
         }
     }
 
     fn next(&mut self) -> Option<char> {
+        self.splice_lines();
         self.col_number += 1;
+        self.start = self.offset;
         let result = self.chars.next();
         if result == Some('\n') {
             self.line_number += 1;
             self.col_number = 0;
         }
+        self.offset += result.map_or(0, char::len_utf8);
+        self.end = self.offset;
 
         result
     }
 
+    /**
+    Skips backslash-newline pairs, the lines around them form one logical line
+    */
+    fn splice_lines(&mut self) {
+        while self.chars.peek() == Some(&'\\') {
+            let mut ahead = self.chars.clone();
+            ahead.next();
+            if ahead.next() != Some('\n') {
+                break;
+            }
+            self.chars.next();
+            self.chars.next();
+            self.line_number += 1;
+            self.col_number = 0;
+            self.offset += 2;
+        }
+    }
+
+    /**
+    Span of the last character read, scanning errors are placed at it
+    */
+    fn here(&self) -> Span {
+        Span::new(self.file, self.start, self.end, self.line_number, self.col_number)
+    }
+
+    fn error(&self, message: String) -> CompilerErrorKind {
+        CompilerErrorKind::ScannerError(self.here(), message)
+    }
+
     fn c_match(&mut self, target: char) -> bool {
         match self.peek() {
-            None => false,
-            Some(c) => {
-                if *c == target {
-                    self.next();
-                    true
-                } else {
-                    false
-                }
+            Some(c) if *c == target => {
+                self.next();
+                true
             }
+            _ => false,
         }
     }
 
     fn peek(&mut self) -> Option<&char> {
+        self.splice_lines();
         self.chars.peek()
     }
 }
 
+/**
+ Bytes of a character or string constant up to the closing `target` quote, right after the
+ opening one, escape sequences are decoded and other characters kept as UTF-8.
+
+ A constant without its closing quote on the line is reported where it starts. A bad escape
+ sequence is reported once the constant is read, so that scanning goes on after it
+ */
 fn parse_constant_chars(
     chars: &mut ScannerPeekable,
     target: char,
-) -> Result<String> {
-    let mut result = String::new();
-    let mut have_slash: bool = false;
-    while let Some(next_char) = chars.peek() {
-        if *next_char == '\n' {
-            // TODO: raise errors, string / char constants does not allow new spaces
-            return Err(CompilerErrorKind::ScannerError(
-                chars.line_number,
-                chars.col_number,
-                String::from("Cannot have '\n' between quotes."),
-            ));
-        } else {
-            if *next_char == '\\' {
-                have_slash = !have_slash;
-            } else if *next_char == target {
-                if !have_slash {
-                    chars.next();
-                    break;
-                }
-                have_slash = false;
+) -> Result<Vec<u8>> {
+    let start = chars.here();
+    let mut result = vec!();
+    let mut error = None;
+    loop {
+        match chars.peek() {
+            Some('\n') | None => {
+                let message = format!("Missing terminating {} character.", target);
+                return Err(CompilerErrorKind::ScannerError(start, message));
+            },
+            Some(c) if *c == target => {
+                chars.next();
+                break;
+            },
+            Some(_) => {},
+        }
+
+        let c = chars.next().unwrap();
+        if c == '\\' {
+            match parse_escape_sequence(chars) {
+                Ok(byte) => result.push(byte),
+                Err(e) => {
+                    error.get_or_insert(e);
+                },
             }
+        } else {
+            result.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+    }
+    match error {
+        Some(error) => Err(error),
+        None => Ok(result),
+    }
+}
+
+/**
+ Skips a line comment up to the end of its line
+ */
+fn skip_line_comment(chars: &mut ScannerPeekable) {
+    while chars.peek().is_some_and(|c| *c != '\n') {
+        chars.next();
+    }
+}
+
+/**
+ Skips a block comment, right after its opening at `start`. Comments do not nest: the first
+ closing ends it, an opening inside is only warned about
+ */
+fn skip_block_comment(chars: &mut ScannerPeekable, start: Span, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
+    loop {
+        match chars.next() {
+            Some('*') if chars.c_match('/') => return Ok(()),
+            Some('/') if chars.peek() == Some(&'*') => {
+                let diagnostic = Diagnostic::warning(String::from("'/*' within block comment."))
+                    .with_code("W0101")
+                    .at(chars.here())
+                    .with_note(String::from("Block comments do not nest, the first '*/' ends the comment."));
+                diagnostics.push(diagnostic);
+            },
+            Some(_) => {},
+            None => return Err(CompilerErrorKind::ScannerError(start, String::from("Unterminated comment."))),
         }
-        result.push(chars.next().unwrap());
-        // TODO: add this char into the constant scope
     }
-    Ok(result)
+}
+
+/**
+ The byte denoted by an escape sequence, right after its backslash
+ */
+fn parse_escape_sequence(chars: &mut ScannerPeekable) -> Result<u8> {
+    let at = chars.here();
+    let error = |message: String| CompilerErrorKind::ScannerError(at, message);
+
+    let Some(c) = chars.peek().copied() else {
+        return Err(error(String::from("Missing escape sequence after '\\'.")));
+    };
+    let simple = match c {
+        'n' => Some(b'\n'),
+        't' => Some(b'\t'),
+        'r' => Some(b'\r'),
+        'a' => Some(0x07),
+        'b' => Some(0x08),
+        'f' => Some(0x0c),
+        'v' => Some(0x0b),
+        '\\' | '\'' | '"' | '?' => Some(c as u8),
+        _ => None,
+    };
+    if let Some(byte) = simple {
+        chars.next();
+        return Ok(byte);
+    }
+
+    // numeric escapes: up to three octal digits, or any number of hexadecimal digits after `x`
+    let (radix, max_digits, name) = match c {
+        '0'..='7' => (8, 3, "Octal"),
+        'x' => {
+            chars.next();
+            (16, usize::MAX, "Hex")
+        },
+        other => return Err(error(format!("Unknown escape sequence '\\{}'.", other))),
+    };
+    let mut value: u32 = 0;
+    let mut digits = 0;
+    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(radix)) {
+        if digits == max_digits {
+            break;
+        }
+        chars.next();
+        value = value.saturating_mul(radix).saturating_add(digit);
+        digits += 1;
+    }
+
+    if digits == 0 {
+        return Err(error(String::from("\\x used with no following hex digits.")));
+    }
+    u8::try_from(value).map_err(|_| error(format!("{} escape sequence out of range.", name)))
 }
 
 impl<'a> Scanner<'a> {
@@ -96,17 +267,101 @@ impl<'a> Scanner<'a> {
             keyword_trie: KeywordTrie::new(),
             number_parser: NumberParser::new(),
             chars: ScannerPeekable::new(source_code.get_source_code().chars().peekable()),
+            text: source_code.get_source_code(),
+            token_start: (1, 0),
+            token_offset: 0,
+            line_break: true,
+            space: false,
+            line_start: false,
+            leading_space: false,
+            directive_hash: false,
+            header_name: false,
+            diagnostics: vec!(),
         }
     }
 
+    pub fn set_file(&mut self, file: usize) {
+        self.chars.file = file;
+    }
+
     pub fn get_position(&self) -> (usize, usize) {
         (self.chars.line_number, self.chars.col_number)
     }
 
+    /**
+    Line and column of the first character of the last token
+    */
+    pub fn get_token_start(&self) -> (usize, usize) {
+        self.token_start
+    }
+
+    /**
+    Span of the last token, up to where scanning stopped if it failed
+    */
+    pub fn get_token_span(&self) -> Span {
+        let (line, column) = self.token_start;
+        Span::new(self.chars.file, self.token_offset, self.chars.end, line, column)
+    }
+
+    /**
+    Whether the last token is the first one on its line, the start of the source included
+    */
+    pub fn is_line_start(&self) -> bool {
+        self.line_start
+    }
+
+    pub fn has_leading_space(&self) -> bool {
+        self.leading_space
+    }
+
+    /**
+    Source text of the last token, without line splices
+    */
+    pub fn get_spelling(&self) -> String {
+        self.text[self.token_offset..self.chars.end].replace("\\\n", "")
+    }
+
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
     pub fn scan(&mut self) -> Result<Token> {
+        let token = self.scan_token()?;
+        let token_type = token.get_token_type();
+        if token_type == TokenType::Eof {
+            // reading past the end moved the column onto where Eof is
+            self.token_start = self.get_position();
+            self.token_offset = self.chars.end;
+        }
+        let token = token.with_span(self.get_token_span());
+        self.header_name = self.directive_hash
+            && token_type == TokenType::Identifier
+            && token.get_text() == "include";
+        self.directive_hash = token_type == TokenType::Hash && self.line_start;
+        Ok(token)
+    }
+
+    /**
+    `<name>` right after `#include`, the characters up to `>` are taken as they are
+    */
+    fn scan_header_name(&mut self) -> Result<Token> {
+        let start = self.chars.here();
+        let mut name = String::new();
+        loop {
+            match self.chars.peek() {
+                Some('>') => {
+                    self.chars.next();
+                    return Ok(Token::header_name_token(name.into_bytes()));
+                },
+                Some('\n') | None => {
+                    return Err(CompilerErrorKind::ScannerError(start, String::from("Missing terminating '>' character.")));
+                },
+                Some(_) => name.push(self.chars.next().unwrap()),
+            }
+        }
+    }
 
-        let mut block_comment = 0;
-        let mut line_comment = 0;
+    fn scan_token(&mut self) -> Result<Token> {
 
         let mut keyword_checker = self.keyword_trie.into_checker();
         let mut number_checker = self.number_parser.into_checker();
@@ -114,21 +369,17 @@ impl<'a> Scanner<'a> {
             match self.chars.next() {
                 Some(c) => {
 
-                    if line_comment > 0 {
-                        if c == '\n' {
-                            line_comment = 0;
-                        }
-                        continue;
-                    }
-                    if block_comment > 0 {
-                        if c == '*' && self.chars.c_match('/') {
-                            block_comment -= 1;
-                        }
+                    if c.is_whitespace() {
+                        self.line_break |= c == '\n';
+                        self.space = true;
                         continue;
                     }
+                    self.token_start = self.get_position();
+                    self.token_offset = self.chars.offset - c.len_utf8();
+                    self.line_start = std::mem::take(&mut self.line_break);
+                    self.leading_space = std::mem::take(&mut self.space);
 
-                    if c.is_whitespace() {
-                    } else if c.is_ascii_alphabetic() || c == '_' {
+                    if c.is_ascii_alphabetic() || c == '_' {
                         // allowed chars [a-zA-Z_]{1}[a-zA-Z_0-9]*
                         keyword_checker.update(c);
                         while let Some(next_chars) = self.chars.peek() {
@@ -141,43 +392,56 @@ impl<'a> Scanner<'a> {
                             Ok(Token::single_token(keyword))
                         } else {
                             let str = keyword_checker.get_str()?;
-                            Ok(Token::Identifier(str))
+                            Ok(Token::identifier(&str))
                         }
-                    } else if c.is_digit(10) {
+                    } else if c.is_ascii_digit() || (c == '.' && self.chars.peek().is_some_and(char::is_ascii_digit)) {
                         number_checker.update(c);
-                        // allowed chars [0-9]{1}[0-9a-z.]*, delegate to number checker for validation checks
+                        // allowed chars [0-9.]{1}[0-9a-zA-Z_.]* plus the sign of an exponent, delegate to number checker for validation checks
                         while let Some(next_chars) = self.chars.peek() {
                             if !number_checker.can_consume(next_chars) {
                                 break;
                             }
                             number_checker.update(self.chars.next().unwrap());
                         }
-                        if let Ok(value) = number_checker.check() {
-                            // a valid token
-                            return Ok(Token::number_token(value));
-                        } else {
-                            // TODO: raise number parse errors
-                        }
-                    } else if c == '"' || c == '\'' {
-                        // TODO: string constants
-                        let str = parse_constant_chars(&mut self.chars, c)?;
-                        match c {
-                            '\'' => return Ok(Token::text_token(TokenType::Char, &str)),
-                            '"' => return Ok(Token::text_token(TokenType::String, &str)),
-                            _ => {panic!("This branch should not happen!")}
-                        }
+                        return number_checker.check()
+                            .map_err(|message| CompilerErrorKind::ScannerError(self.get_token_span(), message));
+                    } else if c == '"' {
+                        let bytes = parse_constant_chars(&mut self.chars, c)?;
+                        return Ok(Token::string_token(bytes));
+                    } else if c == '\'' {
+                        let bytes = parse_constant_chars(&mut self.chars, c)?;
+                        return match bytes.as_slice() {
+                            // char is signed, so are the values of character constants
+                            [byte] => Ok(Token::char_token(*byte as i8 as i64)),
+                            [] => Err(CompilerErrorKind::ScannerError(
+                                self.get_token_span(), String::from("Empty character constant.")
+                            )),
+                            _ => Err(CompilerErrorKind::ScannerError(
+                                self.get_token_span(), String::from("Multi-character character constant is not supported.")
+                            )),
+                        };
                     } else {
                         match c {
-                            '#' => return Ok(Token::single_token(TokenType::Hash)),
+                            '#' => {
+                                return if self.chars.c_match('#') {
+                                    Ok(Token::single_token(TokenType::HashHash))
+                                } else {
+                                    Ok(Token::single_token(TokenType::Hash))
+                                }
+                            }
                             '(' => return Ok(Token::single_token(TokenType::LeftParen)),
                             ')' => return Ok(Token::single_token(TokenType::RightParen)),
                             '{' => return Ok(Token::single_token(TokenType::LeftBrace)),
                             '}' => return Ok(Token::single_token(TokenType::RightBrace)),
                             ',' => return Ok(Token::single_token(TokenType::Comma)),
                             ';' => return Ok(Token::single_token(TokenType::Semicolon)),
+                            '?' => return Ok(Token::single_token(TokenType::Question)),
+                            ':' => return Ok(Token::single_token(TokenType::Colon)),
                             '+' => {
                                 return if self.chars.c_match('=') {
                                     Ok(Token::single_token(TokenType::PlusEqual))
+                                } else if self.chars.c_match('+') {
+                                    Ok(Token::single_token(TokenType::PlusPlus))
                                 } else {
                                     Ok(Token::single_token(TokenType::Plus))
                                 }
@@ -185,37 +449,48 @@ impl<'a> Scanner<'a> {
                             '-' => {
                                 return if self.chars.c_match('=') {
                                     Ok(Token::single_token(TokenType::MinusEqual))
+                                } else if self.chars.c_match('-') {
+                                    Ok(Token::single_token(TokenType::MinusMinus))
                                 } else {
                                     Ok(Token::single_token(TokenType::Minus))
                                 }
                             }
+                            // `*/` out of a comment is `*` `/`, as in `a*/*b*/c`
                             '*' => {
-                                return if self.chars.c_match('/') {
-                                    Err(CompilerErrorKind::ScannerError(
-                                        self.chars.line_number,
-                                        self.chars.col_number,
-                                        String::from("unrecognized end of comment '*/'")
-                                    ))
-                                } else if self.chars.c_match('=') {
+                                return if self.chars.c_match('=') {
                                     Ok(Token::single_token(TokenType::StarEqual))
                                 } else {
                                     Ok(Token::single_token(TokenType::Star))
                                 }
                             }
                             '/' => {
+                                let slash = self.chars.here();
                                 if self.chars.c_match('=') {
                                     return Ok(Token::single_token(TokenType::SlashEqual));
                                 } else if self.chars.c_match('/') {
-                                    line_comment += 1;
-                                } else if self.chars.c_match('*'){
-                                    block_comment += 1;
+                                    skip_line_comment(&mut self.chars);
+                                } else if self.chars.c_match('*') {
+                                    let start = slash.to(self.chars.here());
+                                    skip_block_comment(&mut self.chars, start, &mut self.diagnostics)?;
                                 } else {
                                     return Ok(Token::single_token(TokenType::Slash));
                                 }
+                                // a comment is whitespace, the layout belongs to the next token
+                                self.line_break = self.line_start;
+                                self.space = true;
+                            }
+                            '%' => {
+                                return if self.chars.c_match('=') {
+                                    Ok(Token::single_token(TokenType::PercentEqual))
+                                } else {
+                                    Ok(Token::single_token(TokenType::Percent))
+                                }
                             }
                             '&' => {
                                 return if self.chars.c_match('=') {
                                     Ok(Token::single_token(TokenType::AndEqual))
+                                } else if self.chars.c_match('&') {
+                                    Ok(Token::single_token(TokenType::AndAnd))
                                 } else {
                                     Ok(Token::single_token(TokenType::And))
                                 }
@@ -223,6 +498,8 @@ impl<'a> Scanner<'a> {
                             '|' => {
                                 return if self.chars.c_match('=') {
                                     Ok(Token::single_token(TokenType::OrEqual))
+                                } else if self.chars.c_match('|') {
+                                    Ok(Token::single_token(TokenType::OrOr))
                                 } else {
                                     Ok(Token::single_token(TokenType::Or))
                                 }
@@ -234,13 +511,7 @@ impl<'a> Scanner<'a> {
                                     Ok(Token::single_token(TokenType::Cap))
                                 }
                             }
-                            '~' => {
-                                return if self.chars.c_match('=') {
-                                    Ok(Token::single_token(TokenType::WaveEqual))
-                                } else {
-                                    Ok(Token::single_token(TokenType::Wave))
-                                }
-                            }
+                            '~' => return Ok(Token::single_token(TokenType::Wave)),
                             '=' => {
                                 return if self.chars.c_match('=') {
                                     Ok(Token::single_token(TokenType::EqualEqual))
@@ -259,26 +530,31 @@ impl<'a> Scanner<'a> {
                                 return if self.chars.c_match('=') {
                                     Ok(Token::single_token(TokenType::GreaterEqual))
                                 } else if self.chars.c_match('>') {
-                                    Ok(Token::single_token(TokenType::RightArrow))
+                                    if self.chars.c_match('=') {
+                                        Ok(Token::single_token(TokenType::RightArrowEqual))
+                                    } else {
+                                        Ok(Token::single_token(TokenType::RightArrow))
+                                    }
                                 } else {
                                     Ok(Token::single_token(TokenType::Greater))
                                 }
                             }
+                            '<' if self.header_name => return self.scan_header_name(),
                             '<' => {
                                 return if self.chars.c_match('=') {
                                     Ok(Token::single_token(TokenType::LessEqual))
                                 } else if self.chars.c_match('<') {
-                                    Ok(Token::single_token(TokenType::LeftArrow))
+                                    if self.chars.c_match('=') {
+                                        Ok(Token::single_token(TokenType::LeftArrowEqual))
+                                    } else {
+                                        Ok(Token::single_token(TokenType::LeftArrow))
+                                    }
                                 } else {
                                     Ok(Token::single_token(TokenType::Less))
                                 }
                             }
                             _ => {
-                                return Err(CompilerErrorKind::ScannerError(
-                                    self.chars.line_number,
-                                    self.chars.col_number,
-                                    String::from(format!("unrecognized character: '{}'", c)),
-                                ))
+                                return Err(self.chars.error(format!("Unrecognized character '{}'.", c)))
                             }
                         }
                     }
@@ -290,3 +566,187 @@ impl<'a> Scanner<'a> {
         }
     }
 }
+
+impl TokenStream for Scanner<'_> {
+    fn scan(&mut self) -> Result<Token> {
+        Scanner::scan(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_types(text: &str) -> Vec<TokenType> {
+        let source_code = SourceCode::new(text.to_string());
+        let mut scanner = source_code.into_scanner();
+        let mut types = vec!();
+        loop {
+            let token = scanner.scan().unwrap();
+            types.push(token.get_token_type());
+            if token.get_token_type() == TokenType::Eof {
+                break;
+            }
+        }
+        types
+    }
+
+    #[test]
+    fn test_increment_and_decrement() {
+        assert_eq!(
+            scan_types("++a - -b--+=~c"),
+            vec![
+                TokenType::PlusPlus, TokenType::Identifier, TokenType::Minus, TokenType::Minus,
+                TokenType::Identifier, TokenType::MinusMinus, TokenType::PlusEqual, TokenType::Wave,
+                TokenType::Identifier, TokenType::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shift_and_remainder() {
+        assert_eq!(
+            scan_types("a << b >>= c % d <<= e %= f >> g"),
+            vec![
+                TokenType::Identifier, TokenType::LeftArrow, TokenType::Identifier,
+                TokenType::RightArrowEqual, TokenType::Identifier, TokenType::Percent,
+                TokenType::Identifier, TokenType::LeftArrowEqual, TokenType::Identifier,
+                TokenType::PercentEqual, TokenType::Identifier, TokenType::RightArrow,
+                TokenType::Identifier, TokenType::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_logical_and_bitwise_operators() {
+        assert_eq!(
+            scan_types("a && b & c &= d"),
+            vec![
+                TokenType::Identifier, TokenType::AndAnd, TokenType::Identifier, TokenType::And,
+                TokenType::Identifier, TokenType::AndEqual, TokenType::Identifier, TokenType::Eof,
+            ]
+        );
+        assert_eq!(
+            scan_types("a || b | c |= !d"),
+            vec![
+                TokenType::Identifier, TokenType::OrOr, TokenType::Identifier, TokenType::Or,
+                TokenType::Identifier, TokenType::OrEqual, TokenType::Not, TokenType::Identifier,
+                TokenType::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_preprocessing_tokens() {
+        let source_code = SourceCode::new(String::from("a\\\nb ## #\n /* x\n */ <x.h>\n#include <x.h>"));
+        let mut scanner = source_code.into_scanner();
+        assert_eq!(scanner.scan().unwrap().get_text(), "ab");
+        assert_eq!(scanner.get_spelling(), "ab");
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::HashHash);
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::Hash);
+        assert!(!scanner.is_line_start());
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::Less);
+        assert!(scanner.is_line_start() && scanner.has_leading_space());
+        assert_eq!(scanner.get_token_start(), (4, 5));
+        scanner.scan().unwrap();
+        // '.' is only scanned in numbers and header names
+        assert!(scanner.scan().is_err());
+        scanner.scan().unwrap();
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::Greater);
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::Hash);
+        assert_eq!(scanner.scan().unwrap().get_text(), "include");
+        assert_eq!(scanner.scan().unwrap().get_bytes(), b"x.h");
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let source_code = SourceCode::new(String::from("int x =\\\n 10;\n"));
+        let mut scanner = source_code.into_scanner();
+        scanner.set_file(3);
+        let spans: Vec<Span> = (0..6).map(|_| scanner.scan().unwrap().span).collect();
+        assert_eq!(spans, vec![
+            Span::new(3, 0, 3, 1, 1),
+            Span::new(3, 4, 5, 1, 5),
+            Span::new(3, 6, 7, 1, 7),
+            Span::new(3, 10, 12, 2, 2),
+            Span::new(3, 12, 13, 2, 4),
+            Span::new(3, 14, 14, 3, 1),
+        ]);
+    }
+
+    #[test]
+    fn test_floating_constants() {
+        assert_eq!(
+            scan_types("1.5 .5 1e-3 2.f 3 1e+2-x"),
+            vec![
+                TokenType::Double, TokenType::Double, TokenType::Double, TokenType::Float,
+                TokenType::Integer, TokenType::Double, TokenType::Minus, TokenType::Identifier,
+                TokenType::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_number_position() {
+        let source_code = SourceCode::new(String::from("x\n  09;"));
+        let mut scanner = source_code.into_scanner();
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::Identifier);
+        match scanner.scan() {
+            Err(CompilerErrorKind::ScannerError(span, message)) => {
+                assert_eq!((span.line, span.column, span.start, span.end), (2, 3, 4, 6));
+                assert!(message.contains("octal"));
+            },
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        let source_code = SourceCode::new(String::from(r#"'\n' '\377' "a\tb\x41\101\"\\" '\q'"#));
+        let mut scanner = source_code.into_scanner();
+        assert_eq!(scanner.scan().unwrap().get_value() as i64, 10);
+        assert_eq!(scanner.scan().unwrap().get_value() as i64, -1);
+        assert_eq!(scanner.scan().unwrap().get_bytes(), b"a\tbAA\"\\");
+        assert!(matches!(scanner.scan(), Err(CompilerErrorKind::ScannerError(Span { line: 1, column: 33, .. }, _))));
+    }
+
+    fn error_position(result: Result<Token>) -> (usize, usize, String) {
+        match result {
+            Err(CompilerErrorKind::ScannerError(span, message)) => (span.line, span.column, message),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_recovery() {
+        // each error is reported where the character or constant starts, scanning goes on after it
+        let source_code = SourceCode::new(String::from("a @@ \"b\\q\" c\n\"d\n 'e\n f"));
+        let mut scanner = source_code.into_scanner();
+        assert_eq!(scanner.scan().unwrap().get_text(), "a");
+        assert_eq!(error_position(scanner.scan()), (1, 3, String::from("Unrecognized character '@'.")));
+        assert_eq!(error_position(scanner.scan()), (1, 4, String::from("Unrecognized character '@'.")));
+        assert_eq!(error_position(scanner.scan()), (1, 8, String::from("Unknown escape sequence '\\q'.")));
+        assert_eq!(scanner.scan().unwrap().get_text(), "c");
+        assert_eq!(error_position(scanner.scan()), (2, 1, String::from("Missing terminating \" character.")));
+        assert_eq!(error_position(scanner.scan()), (3, 2, String::from("Missing terminating ' character.")));
+        assert_eq!(scanner.scan().unwrap().get_text(), "f");
+    }
+
+    #[test]
+    fn test_comments() {
+        // comments do not nest, and `*/` out of one is two tokens
+        let source_code = SourceCode::new(String::from("a /* /* */ */b\n  /* c"));
+        let mut scanner = source_code.into_scanner();
+        assert_eq!(scanner.scan().unwrap().get_text(), "a");
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::Star);
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::Slash);
+        assert_eq!(scanner.scan().unwrap().get_text(), "b");
+        assert_eq!(error_position(scanner.scan()), (2, 3, String::from("Unterminated comment.")));
+        assert_eq!(scanner.scan().unwrap().get_token_type(), TokenType::Eof);
+
+        let warnings = scanner.take_diagnostics();
+        assert_eq!(warnings.len(), 1);
+        let span = warnings[0].primary_span().unwrap();
+        assert_eq!((span.line, span.column), (1, 6));
+    }
+}
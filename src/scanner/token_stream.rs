@@ -0,0 +1,11 @@
+use crate::common::Result;
+use crate::scanner::tokens::Token;
+
+/**
+ <p> A source of tokens for the parser: the scanner itself, or the preprocessor in front of it. </p>
+ */
+pub trait TokenStream {
+
+    fn scan(&mut self) -> Result<Token>;
+
+}
@@ -1,4 +1,6 @@
 use std::fmt::{Debug, Formatter};
+use crate::common::span::Span;
+use crate::parser::data_type::DataType;
 
 #[derive(Clone, Debug, PartialEq, Copy)]
 pub enum TokenType {
@@ -8,14 +10,28 @@ pub enum TokenType {
     RightBrace,
     Comma,
     Semicolon,
+    Question,
+    Colon,
     LeftArrow,
+    LeftArrowEqual,
     RightArrow,
+    RightArrowEqual,
 
     Identifier,
     Char,
     String,
+    /**
+    `<name>` of an `#include` directive
+    */
+    HeaderName,
+    /**
+    Text the scanner could not make a token of, kept by the preprocessor with the error message:
+    it is only an error if it reaches the parser
+    */
+    Invalid,
     Integer,
     Double,
+    Float,
 
     Eof,
     None,
@@ -23,22 +39,28 @@ pub enum TokenType {
     // Operators
     Plus,
     PlusEqual,
+    PlusPlus,
     Minus,
     MinusEqual,
+    MinusMinus,
     Star,
     StarEqual,
     Slash,
     SlashEqual,
+    Percent,
+    PercentEqual,
     And,
     AndEqual,
+    AndAnd,
     Or,
     OrEqual,
+    OrOr,
     Cap,
     CapEqual,
     Wave,
-    WaveEqual,
     Not,
     Hash,
+    HashHash,
 
     Greater,
     GreaterEqual,
@@ -50,8 +72,13 @@ pub enum TokenType {
     NotEqual,
 
     // type keywords,
+    KeyVoid,
+    KeyChar,
+    KeyShort,
     KeyInt,
     KeyLong,
+    KeySigned,
+    KeyUnsigned,
     KeyDouble,
     KeyFloat,
     KeyString,
@@ -64,6 +91,7 @@ pub enum TokenType {
     Else,
     For,
     While,
+    Do,
 
     // Test keyword
     Print,
@@ -71,86 +99,183 @@ pub enum TokenType {
 
 
 #[derive(Clone, Debug)]
-#[repr(C)]
-pub struct TokenValue {
-    value: Box<u64>,
-}
-
-impl TokenValue {
-    pub fn get_value(&self) -> u64 {
-        *self.value
-    }
+pub enum TokenValue {
+    /**
+    Value of an integer constant, and its type given by the suffix and the magnitude
+    */
+    Integer(u64, DataType),
+    Floating(f64),
 }
 
+/**
+ What a token is, apart from where it is
+ */
 #[derive(Clone)]
-pub enum Token {
+pub enum TokenKind {
     None,
     Single(TokenType),
     Identifier(String),
     Number(TokenType, TokenValue),
-    Text(TokenType, Box<String>),
+    /**
+    String literal, with its escape sequences decoded
+    */
+    Text(TokenType, Vec<u8>),
+}
+
+#[derive(Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
 }
 
 impl Debug for Token {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Token::Single(tt) => write!(f, "[{:?}]", tt),
-            Token::Identifier(ident) => write!(f, "[Identifier: {}]", ident),
-            Token::Number(tt, value) => {
+        match &self.kind {
+            TokenKind::Single(tt) => write!(f, "[{:?}]", tt),
+            TokenKind::Identifier(ident) => write!(f, "[Identifier: {}]", ident),
+            TokenKind::Number(tt, value) => {
                 match tt {
                     TokenType::Integer => write!(f, "[Int: {:?}]", value),
                     TokenType::Double => write!(f, "[Double: {:?}]", value),
+                    TokenType::Float => write!(f, "[Float: {:?}]", value),
+                    TokenType::Char => write!(f, "[Char: {:?}]", value),
                     _ => panic!("Should not happen"),
                 }
             },
-            Token::Text(tt, value) => {
+            TokenKind::Text(tt, value) => {
                 match tt {
-                    TokenType::String => write!(f, "[Str: {:?}]", value),
-                    TokenType::Char => write!(f, "[Char: {:?}]", value),
+                    TokenType::String => write!(f, "[Str: \"{}\"]", value.escape_ascii()),
+                    TokenType::HeaderName => write!(f, "[Header: <{}>]", value.escape_ascii()),
+                    TokenType::Invalid => write!(f, "[Invalid: {}]", value.escape_ascii()),
                     _ => panic!("Should not happen!"),
                 }
             },
-            Token::None => write!(f, "[None]"),
-            other => unimplemented!("{:?}", other),
+            TokenKind::None => write!(f, "[None]"),
         }
     }
 }
 
 impl<'a> Token {
+    fn from_kind(kind: TokenKind) -> Self {
+        Self {
+            kind,
+            span: Span::default(),
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::from_kind(TokenKind::None)
+    }
+
     pub fn single_token(token_type: TokenType) -> Self {
-        Self::Single(token_type)
+        Self::from_kind(TokenKind::Single(token_type))
     }
 
-    pub fn number_token(value: i64) -> Self {
-        Self::Number(TokenType::Integer, TokenValue { value: Box::new(value as u64) })
+    pub fn number_token(value: u64, data_type: DataType) -> Self {
+        Self::from_kind(TokenKind::Number(TokenType::Integer, TokenValue::Integer(value, data_type)))
     }
 
-    pub fn text_token(token_type: TokenType, value: &'a str) -> Self {
-        // token_type must be either String or Char
-        Self::Text(token_type, Box::new(value.to_string()))
+    /**
+    token_type must be either Double or Float
+    */
+    pub fn float_token(token_type: TokenType, value: f64) -> Self {
+        Self::from_kind(TokenKind::Number(token_type, TokenValue::Floating(value)))
+    }
+
+    pub fn string_token(value: Vec<u8>) -> Self {
+        Self::from_kind(TokenKind::Text(TokenType::String, value))
+    }
+
+    pub fn header_name_token(name: Vec<u8>) -> Self {
+        Self::from_kind(TokenKind::Text(TokenType::HeaderName, name))
+    }
+
+    /**
+    Text the scanner failed on, holding the error message
+    */
+    pub fn invalid_token(message: String) -> Self {
+        Self::from_kind(TokenKind::Text(TokenType::Invalid, message.into_bytes()))
+    }
+
+    /**
+    Character constants are integer constants of type int
+    */
+    pub fn char_token(value: i64) -> Self {
+        Self::from_kind(TokenKind::Number(TokenType::Char, TokenValue::Integer(value as u64, DataType::Int)))
     }
 
     pub fn identifier(text: &'a str) -> Self {
         // identifiers
-        Self::Identifier(text.to_string())
+        Self::from_kind(TokenKind::Identifier(text.to_string()))
+    }
+
+    /**
+    The same token placed at a span
+    */
+    pub fn with_span(self, span: Span) -> Self {
+        Self {
+            span,
+            ..self
+        }
     }
 
     pub fn get_token_type(&self) -> TokenType {
-        match self {
-            Token::Single(t) => *t,
-            Token::Identifier(_) => TokenType::Identifier,
-            Token::Number(t, _) => *t,
-            Token::Text(t, _) => *t,
-            Token::None => TokenType::None,
+        match &self.kind {
+            TokenKind::Single(t) => *t,
+            TokenKind::Identifier(_) => TokenType::Identifier,
+            TokenKind::Number(t, _) => *t,
+            TokenKind::Text(t, _) => *t,
+            TokenKind::None => TokenType::None,
+        }
+    }
+
+    pub fn get_text(&self) -> &str {
+        match &self.kind {
+            TokenKind::Identifier(text) => text,
+            _ => unimplemented!(
+                "get_text() is not implemented for {:?}",
+                self)
+        }
+    }
+
+    pub fn get_bytes(&self) -> &[u8] {
+        match &self.kind {
+            TokenKind::Text(_, bytes) => bytes,
+            _ => unimplemented!(
+                "get_bytes() is not implemented for {:?}",
+                self)
         }
     }
 
     pub fn get_value(&self) -> u64 {
-        match self {
-            Token::Number(_, value) => value.get_value(),
-            other => unimplemented!(
+        match &self.kind {
+            TokenKind::Number(_, TokenValue::Integer(value, _)) => *value,
+            _ => unimplemented!(
                 "get_value() is not implemented for {:?}",
-                other)
+                self)
+        }
+    }
+
+    /**
+    Type of a numeric constant
+    */
+    pub fn get_data_type(&self) -> DataType {
+        match &self.kind {
+            TokenKind::Number(_, TokenValue::Integer(_, data_type)) => *data_type,
+            TokenKind::Number(TokenType::Float, _) => DataType::Float,
+            TokenKind::Number(_, TokenValue::Floating(_)) => DataType::Double,
+            _ => unimplemented!(
+                "get_data_type() is not implemented for {:?}",
+                self)
+        }
+    }
+
+    pub fn get_float_value(&self) -> f64 {
+        match &self.kind {
+            TokenKind::Number(_, TokenValue::Floating(value)) => *value,
+            _ => unimplemented!(
+                "get_float_value() is not implemented for {:?}",
+                self)
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod type_checker;
@@ -0,0 +1,452 @@
+use crate::common::errors::error::CompilerErrorKind;
+use crate::common::span::Span;
+use crate::parser::ast::assignment_node::AssignmentNode;
+use crate::parser::ast::binary_op_node::BinaryAstNode;
+use crate::parser::ast::block_node::BlockNode;
+use crate::parser::ast::call_node::CallNode;
+use crate::parser::ast::cast_node::CastNode;
+use crate::parser::ast::core::AstNode;
+use crate::parser::ast::declaration_node::DeclarationNode;
+use crate::parser::ast::do_while_node::DoWhileNode;
+use crate::parser::ast::expression_statement_node::ExpressionStatementNode;
+use crate::parser::ast::float_value_node::FloatValueNode;
+use crate::parser::ast::for_node::ForNode;
+use crate::parser::ast::function_node::FunctionNode;
+use crate::parser::ast::identifier_node::IdentifierNode;
+use crate::parser::ast::if_node::IfNode;
+use crate::parser::ast::logical_op_node::LogicalAstNode;
+use crate::parser::ast::loop_control_node::LoopControlNode;
+use crate::parser::ast::print_node::PrintAstNode;
+use crate::parser::ast::program_node::ProgramNode;
+use crate::parser::ast::return_node::ReturnNode;
+use crate::parser::ast::string_node::StringNode;
+use crate::parser::ast::unary_op_node::UnaryAstNode;
+use crate::parser::ast::value_node::ValueNode;
+use crate::parser::ast::while_node::WhileNode;
+use crate::parser::data_type::DataType;
+use crate::parser::operations::Operations;
+use crate::symbols::symbol_table::{SymbolId, SymbolKind, SymbolTable};
+
+/**
+ <p> Semantic analysis between parsing and code generation. </p>
+
+ Annotates every expression with its C type, applies the integer promotions and the usual
+ arithmetic conversions by wrapping operands in `CastNode`s, and checks that operands are
+ usable by their operators. Errors are collected so that all of them are reported at once.
+ */
+pub struct TypeChecker<'a> {
+    symbols: &'a SymbolTable,
+
+    /**
+    Function whose body is being checked, for `return` statements
+    */
+    function: Option<SymbolId>,
+
+    errors: Vec<CompilerErrorKind>,
+}
+
+impl<'a> TypeChecker<'a> {
+    pub fn new(symbols: &'a SymbolTable) -> Self {
+        Self {
+            symbols,
+            function: None,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn check(mut self, ast: &mut Box<dyn AstNode>) -> Result<(), Vec<CompilerErrorKind>> {
+        self.statement(ast);
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    fn error(&mut self, span: Span, message: String) {
+        self.errors.push(CompilerErrorKind::SemanticError(span, message));
+    }
+
+    fn statement(&mut self, node: &mut Box<dyn AstNode>) {
+        if let Some(program_node) = node.downcast_mut::<ProgramNode>() {
+            for declaration in program_node.declarations.iter_mut() {
+                self.statement(declaration);
+            }
+        } else if let Some(function_node) = node.downcast_mut::<FunctionNode>() {
+            if let Some(body) = &mut function_node.body {
+                self.function = Some(function_node.symbol);
+                self.statement(body);
+                self.function = None;
+            }
+        } else if let Some(block_node) = node.downcast_mut::<BlockNode>() {
+            for statement in block_node.block.iter_mut() {
+                self.statement(statement);
+            }
+        } else if let Some(declaration_node) = node.downcast_mut::<DeclarationNode>() {
+            for declarator in declaration_node.declarators.iter_mut() {
+                if let Some(init) = &mut declarator.init {
+                    if let Some(data_type) = self.value(init, "initializer") {
+                        self.assign(init, data_type, declarator.data_type, "initialization");
+                    }
+                }
+            }
+        } else if let Some(statement_node) = node.downcast_mut::<ExpressionStatementNode>() {
+            // the value is discarded, void is fine
+            self.expression(&mut statement_node.expr);
+        } else if let Some(print_node) = node.downcast_mut::<PrintAstNode>() {
+            // printed like a variadic argument, after the integer promotions and float to double
+            if let Some(data_type) = self.value(&mut print_node.expr, "print") {
+                let promoted = match data_type {
+                    DataType::Float => DataType::Double,
+                    other => other.promote(),
+                };
+                Self::convert(&mut print_node.expr, promoted);
+            }
+        } else if let Some(return_node) = node.downcast_mut::<ReturnNode>() {
+            self.return_statement(return_node);
+        } else if let Some(if_node) = node.downcast_mut::<IfNode>() {
+            self.condition(&mut if_node.condition);
+            self.statement(&mut if_node.then_branch);
+            if let Some(else_branch) = &mut if_node.else_branch {
+                self.statement(else_branch);
+            }
+        } else if let Some(while_node) = node.downcast_mut::<WhileNode>() {
+            self.condition(&mut while_node.condition);
+            self.statement(&mut while_node.body);
+        } else if let Some(do_while_node) = node.downcast_mut::<DoWhileNode>() {
+            self.statement(&mut do_while_node.body);
+            self.condition(&mut do_while_node.condition);
+        } else if let Some(for_node) = node.downcast_mut::<ForNode>() {
+            if let Some(init) = &mut for_node.init {
+                self.statement(init);
+            }
+            if let Some(condition) = &mut for_node.condition {
+                self.condition(condition);
+            }
+            if let Some(step) = &mut for_node.step {
+                self.statement(step);
+            }
+            self.statement(&mut for_node.body);
+        } else if node.downcast_ref::<LoopControlNode>().is_none() {
+            unimplemented!("{:?}", node);
+        }
+    }
+
+    fn return_statement(&mut self, return_node: &mut ReturnNode) {
+        let Some(function) = self.function else {
+            unreachable!("return outside of a function");
+        };
+        let function = self.symbols.get(function);
+        let span = return_node.get_span();
+
+        match (&mut return_node.value, function.data_type) {
+            (None, DataType::Void) => {},
+            (None, _) => self.error(span, format!(
+                "Non-void function '{}' should return a value.", function.name
+            )),
+            (Some(value), DataType::Void) => {
+                self.expression(value);
+                self.error(value.get_span(), format!(
+                    "Void function '{}' should not return a value.", function.name
+                ));
+            },
+            (Some(value), return_type) => {
+                if let Some(data_type) = self.value(value, "return value") {
+                    self.assign(value, data_type, return_type, "return");
+                }
+            },
+        }
+    }
+
+    /**
+    Checks an expression whose value is used, `context` names the use in the error message.
+    Returns None when the expression is void or erroneous
+    */
+    fn value(&mut self, node: &mut Box<dyn AstNode>, context: &str) -> Option<DataType> {
+        match self.expression(node) {
+            Some(DataType::Void) => {
+                self.error(node.get_span(), format!("Void value used as {}.", context));
+                None
+            },
+            data_type => data_type,
+        }
+    }
+
+    /**
+    Checks an expression used as a truth value, floating values are compared against zero
+    so that code generation only tests integers
+    */
+    fn condition(&mut self, node: &mut Box<dyn AstNode>) -> Option<DataType> {
+        let data_type = self.value(node, "condition")?;
+        if !data_type.is_floating() {
+            return Some(data_type);
+        }
+
+        let operand = std::mem::replace(node, Box::new(BlockNode::new(vec!())));
+        let span = operand.get_span();
+        let mut comparison = BinaryAstNode::new(operand, Box::new(FloatValueNode::new(0.0, data_type)));
+        comparison.set_op(Operations::OpFloatNotEqual);
+        comparison.set_type(DataType::Int);
+        comparison.set_span(span);
+        *node = Box::new(comparison);
+        Some(DataType::Int)
+    }
+
+    /**
+    Annotates the expression with its type, None when it is erroneous and has been reported
+    */
+    fn expression(&mut self, node: &mut Box<dyn AstNode>) -> Option<DataType> {
+        // constants are typed by the parser
+        let data_type = if let Some(value_node) = node.downcast_ref::<ValueNode>() {
+            value_node.get_type()
+        } else if let Some(float_value_node) = node.downcast_ref::<FloatValueNode>() {
+            float_value_node.get_type()
+        } else if let Some(string_node) = node.downcast_ref::<StringNode>() {
+            string_node.get_type()
+        } else if let Some(identifier_node) = node.downcast_ref::<IdentifierNode>() {
+            self.symbols.get(identifier_node.symbol).data_type
+        } else if let Some(call_node) = node.downcast_mut::<CallNode>() {
+            self.call(call_node)?
+        } else if let Some(unary_node) = node.downcast_mut::<UnaryAstNode>() {
+            self.unary(unary_node)?
+        } else if let Some(binary_node) = node.downcast_mut::<BinaryAstNode>() {
+            self.binary(binary_node)?
+        } else if let Some(logical_node) = node.downcast_mut::<LogicalAstNode>() {
+            let left = self.condition(&mut logical_node.left);
+            let right = self.condition(&mut logical_node.right);
+            left.and(right)?;
+            DataType::Int
+        } else if let Some(assignment_node) = node.downcast_mut::<AssignmentNode>() {
+            self.assignment(assignment_node)?
+        } else if let Some(cast_node) = node.downcast_mut::<CastNode>() {
+            self.value(&mut cast_node.operand, "operand")?;
+            cast_node.get_type()
+        } else {
+            unimplemented!("{:?}", node);
+        };
+
+        node.set_type(data_type);
+        Some(data_type)
+    }
+
+    fn call(&mut self, call_node: &mut CallNode) -> Option<DataType> {
+        let function = self.symbols.get(call_node.symbol);
+        let SymbolKind::Function { params, .. } = &function.kind else {
+            unreachable!("the parser only resolves calls to functions");
+        };
+
+        if params.len() != call_node.args.len() {
+            self.error(call_node.get_span(), format!(
+                "Too {} arguments to function '{}', expected {}, have {}.",
+                if call_node.args.len() < params.len() { "few" } else { "many" },
+                function.name,
+                params.len(),
+                call_node.args.len()
+            ));
+        }
+
+        let mut valid = true;
+        for (index, arg) in call_node.args.iter_mut().enumerate() {
+            match self.value(arg, "argument") {
+                Some(data_type) => if let Some(param) = params.get(index) {
+                    valid &= self.assign(arg, data_type, *param, "argument passing");
+                },
+                None => valid = false,
+            }
+        }
+
+        valid.then_some(function.data_type)
+    }
+
+    fn unary(&mut self, unary_node: &mut UnaryAstNode) -> Option<DataType> {
+        let operator = unary_node.get_op();
+        if operator == Operations::OpLogicalNot {
+            self.condition(&mut unary_node.operand)?;
+            return Some(DataType::Int);
+        }
+        let operand = self.value(&mut unary_node.operand, "operand")?;
+
+        match operator {
+            Operations::OpUnaryPlus | Operations::OpNegate | Operations::OpBitNot => {
+                if !operand.is_arithmetic() || (operator.is_integer_only() && !operand.is_integer()) {
+                    self.error(unary_node.get_span(), format!("Invalid operand to unary {:?} ({:?}).", operator, operand));
+                    return None;
+                }
+                let promoted = operand.promote();
+                Self::convert(&mut unary_node.operand, promoted);
+                if promoted.is_floating() {
+                    unary_node.set_op(operator.to_floating());
+                }
+                Some(promoted)
+            },
+            Operations::OpPreIncrement | Operations::OpPreDecrement
+            | Operations::OpPostIncrement | Operations::OpPostDecrement => {
+                if !unary_node.operand.is_lvalue() {
+                    self.error(unary_node.operand.get_span(), format!("Operand of {:?} is not assignable.", operator));
+                    return None;
+                }
+                if !operand.is_arithmetic() {
+                    self.error(unary_node.get_span(), format!("Invalid operand to unary {:?} ({:?}).", operator, operand));
+                    return None;
+                }
+                Some(operand)
+            },
+            other => unimplemented!("{:?}", other),
+        }
+    }
+
+    fn binary(&mut self, binary_node: &mut BinaryAstNode) -> Option<DataType> {
+        let left = self.value(&mut binary_node.left, "operand");
+        let right = self.value(&mut binary_node.right, "operand");
+        let (left, right) = (left?, right?);
+
+        let operator = binary_node.get_op();
+        if left == DataType::CharPointer && right == DataType::CharPointer
+            && matches!(operator, Operations::OpIntEqual | Operations::OpIntNotEqual) {
+            // pointers only compare for identity
+            return Some(DataType::Int);
+        }
+        if !(left.is_arithmetic() && right.is_arithmetic())
+            || (operator.is_integer_only() && !(left.is_integer() && right.is_integer())) {
+            self.error(binary_node.get_span(), format!(
+                "Invalid operands to binary {:?} ({:?} and {:?}).", operator, left, right
+            ));
+            return None;
+        }
+        let (operation_type, result_type) = match operator {
+            // the operands of shifts are promoted independently, the result has the type of the left one
+            Operations::OpIntShiftLeft | Operations::OpIntShiftRight => {
+                Self::convert(&mut binary_node.left, left.promote());
+                Self::convert(&mut binary_node.right, right.promote());
+                (left.promote(), left.promote())
+            },
+            _ => {
+                let common = DataType::common_type(left, right);
+                Self::convert(&mut binary_node.left, common);
+                Self::convert(&mut binary_node.right, common);
+                (common, if operator.is_comparison() { DataType::Int } else { common })
+            },
+        };
+        if operation_type.is_floating() {
+            binary_node.set_op(operator.to_floating());
+        } else if operation_type.is_unsigned() {
+            binary_node.set_op(operator.to_unsigned());
+        }
+
+        Some(result_type)
+    }
+
+    fn assignment(&mut self, assignment_node: &mut AssignmentNode) -> Option<DataType> {
+        let target = self.value(&mut assignment_node.target, "assignment target");
+        let value = self.value(&mut assignment_node.value, "assigned value");
+        if !assignment_node.target.is_lvalue() {
+            self.error(assignment_node.target.get_span(), String::from("Expression is not assignable."));
+            return None;
+        }
+        let (target, value) = (target?, value?);
+
+        let operator = assignment_node.get_op();
+        if operator == Operations::OpAssign {
+            self.assign(&mut assignment_node.value, value, target, "assignment");
+            return Some(target);
+        }
+        if !(target.is_arithmetic() && value.is_arithmetic())
+            || (operator.is_integer_only() && !(target.is_integer() && value.is_integer())) {
+            self.error(assignment_node.get_span(), format!(
+                "Invalid operands to {:?}= ({:?} and {:?}).", operator, target, value
+            ));
+            return None;
+        }
+        let value_type = match operator {
+            // compound assignments operate in the type of `target op value`, the value is converted
+            // to it and the result back to the target type
+            Operations::OpIntShiftLeft | Operations::OpIntShiftRight => target.promote(),
+            _ => DataType::common_type(target, value),
+        };
+        Self::convert(&mut assignment_node.value, value_type);
+        if value_type.is_floating() {
+            assignment_node.set_op(operator.to_floating());
+        } else if value_type.is_unsigned() {
+            assignment_node.set_op(operator.to_unsigned());
+        }
+
+        Some(target)
+    }
+
+    /**
+    Converts a value of type `from` as if by assignment to an object of type `to`, arithmetic
+    types convert into each other and pointers only into the same pointer type.
+    Returns whether the conversion is valid
+    */
+    fn assign(&mut self, node: &mut Box<dyn AstNode>, from: DataType, to: DataType, context: &str) -> bool {
+        if from != to && !(from.is_arithmetic() && to.is_arithmetic()) {
+            self.error(node.get_span(), format!("Incompatible types in {}: {:?} to {:?}.", context, from, to));
+            return false;
+        }
+        Self::convert(node, to);
+        true
+    }
+
+    /**
+    Wraps `node` in an implicit conversion to `data_type`, unless it already has that type
+    */
+    fn convert(node: &mut Box<dyn AstNode>, data_type: DataType) {
+        if node.get_type() == data_type {
+            return;
+        }
+        let operand = std::mem::replace(node, Box::new(BlockNode::new(vec!())));
+        let span = operand.get_span();
+        *node = Box::new(CastNode::new(operand, data_type));
+        node.set_span(span);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parser::Parser;
+    use crate::source_code::SourceCode;
+    use crate::scanner::scanner::Scanner;
+
+    fn check(text: &str) -> Result<(), Vec<CompilerErrorKind>> {
+        let source_code = SourceCode::new(text.to_string());
+        let mut scanner = Scanner::new(&source_code);
+        let mut parser = Parser::new(&mut scanner);
+        let mut ast = parser.parse().unwrap();
+        TypeChecker::new(parser.symbols()).check(&mut ast)
+    }
+
+    #[test]
+    fn test_implicit_conversions() {
+        assert!(check("long f(int a) { long b = a; int c = b + a; return c << b; }").is_ok());
+    }
+
+    #[test]
+    fn test_reports_all_errors() {
+        let text = "void g(void) {}
+            int f(int a) {
+                int b = g();
+                a + 1 = 2;
+                f(1, 2);
+                return;
+            }";
+        let errors = check(text).unwrap_err();
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+    }
+
+    #[test]
+    fn test_floating_operands() {
+        assert!(check("double f(float a, int b) { if (a) return -a * b; return b / 2.5f; }").is_ok());
+
+        let errors = check("int f(double a) { int b = a % 2; b <<= a; return ~a; }").unwrap_err();
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+    }
+
+    #[test]
+    fn test_strings() {
+        assert!(check("int f(char *s) { char *t = \"a\" \"b\"; if (s == t || !s) return 'a'; return f(t); }").is_ok());
+
+        let errors = check("int f(char *s) { int a = s; s = 1; s++; return s + 1; }").unwrap_err();
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::scanner::scanner::Scanner;
 use crate::parser::parser::Parser;
+use crate::scanner::token_stream::TokenStream;
 
 pub struct SourceCode {
 
@@ -19,11 +20,11 @@ impl SourceCode {
         &self.code
     }
 
-    pub fn into_scanner(&self) -> Scanner {
+    pub fn into_scanner(&self) -> Scanner<'_> {
         Scanner::new(self)
     }
 
-    pub fn into_parser<'a>(&'a self, scanner: &'a mut Scanner<'a>) -> Parser<'a> {
+    pub fn into_parser<'a>(&'a self, scanner: &'a mut dyn TokenStream) -> Parser<'a> {
         Parser::new(scanner)
     }
 
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use crate::common::errors::diagnostic::Diagnostic;
+use crate::common::errors::error::CompilerErrorKind;
+use crate::common::span::Span;
+use crate::common::Result;
+use crate::parser::data_type::DataType;
+
+/**
+ Index of a symbol in its `SymbolTable`, stays valid after the scope declaring it is closed
+ */
+pub type SymbolId = usize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolKind {
+    Variable,
+    /**
+    `defined` is set once a body has been seen, a prototype alone leaves it unset
+    */
+    Function { params: Vec<DataType>, defined: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Storage {
+    /**
+    File scope, e.g. functions
+    */
+    Global,
+    /**
+    Automatic variable in the stack frame of a function
+    */
+    Local,
+    /**
+    The `index`-th parameter of a function
+    */
+    Parameter(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScopeKind {
+    File,
+    Function,
+    Block,
+}
+
+#[derive(Debug)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /**
+    Type of a variable, or the return type of a function
+    */
+    pub data_type: DataType,
+    pub storage: Storage,
+    /**
+    Position relative to the frame pointer, assigned by code generation
+    */
+    pub offset: Option<i64>,
+
+    /**
+    Name of the declaration, of the definition for a defined function
+    */
+    pub span: Span,
+}
+
+impl Symbol {
+    pub fn is_function(&self) -> bool {
+        matches!(self.kind, SymbolKind::Function { .. })
+    }
+}
+
+struct Scope {
+    kind: ScopeKind,
+    names: HashMap<String, SymbolId>,
+}
+
+/**
+ <p> Names visible at each point of the program, from the file scope down to the innermost block. </p>
+
+ The parser opens and closes scopes while it walks the source and resolves every identifier to a
+ `SymbolId`, the symbols themselves outlive their scopes so that later passes can look them up.
+ */
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+    scopes: Vec<Scope>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self {
+            symbols: Vec::new(),
+            scopes: vec!(Scope { kind: ScopeKind::File, names: HashMap::new() }),
+        }
+    }
+
+    pub fn enter_scope(&mut self, kind: ScopeKind) {
+        self.scopes.push(Scope { kind, names: HashMap::new() });
+    }
+
+    pub fn leave_scope(&mut self) {
+        debug_assert!(self.scopes.len() > 1, "the file scope is never left");
+        self.scopes.pop();
+    }
+
+    pub fn current_scope(&self) -> ScopeKind {
+        self.scopes.last().unwrap().kind
+    }
+
+    /**
+    Declares a variable in the innermost scope, a name can only be declared once per scope
+    but may shadow the same name from an enclosing scope
+    */
+    pub fn declare_variable(
+        &mut self,
+        name: &str,
+        data_type: DataType,
+        storage: Storage,
+        span: Span,
+    ) -> Result<SymbolId> {
+        if let Some(&previous) = self.scopes.last().unwrap().names.get(name) {
+            return Err(self.redeclaration(name, previous, span));
+        }
+
+        let id = self.push(Symbol {
+            name: name.to_string(),
+            kind: SymbolKind::Variable,
+            data_type,
+            storage,
+            offset: None,
+            span,
+        });
+        self.scopes.last_mut().unwrap().names.insert(name.to_string(), id);
+        Ok(id)
+    }
+
+    /**
+    Declares a function in the file scope, repeated declarations have to agree on the types
+    and at most one of them may come with a body
+    */
+    pub fn declare_function(
+        &mut self,
+        name: &str,
+        return_type: DataType,
+        params: Vec<DataType>,
+        is_definition: bool,
+        span: Span,
+    ) -> Result<SymbolId> {
+        if let Some(&id) = self.scopes[0].names.get(name) {
+            let symbol = &self.symbols[id];
+            let SymbolKind::Function { params: declared, defined } = &symbol.kind else {
+                return Err(self.redeclaration(name, id, span));
+            };
+            if symbol.data_type != return_type || *declared != params {
+                return Err(Diagnostic::error(format!("Conflicting types for '{}'.", name))
+                    .with_code("E0302")
+                    .at(span)
+                    .with_secondary(symbol.span, format!("Previous declaration of '{}' is here.", name))
+                    .into());
+            }
+            if *defined && is_definition {
+                return Err(Diagnostic::error(format!("Redefinition of '{}'.", name))
+                    .with_code("E0303")
+                    .at(span)
+                    .with_secondary(symbol.span, format!("Previous definition of '{}' is here.", name))
+                    .into());
+            }
+            if is_definition {
+                let symbol = &mut self.symbols[id];
+                symbol.kind = SymbolKind::Function { params, defined: true };
+                symbol.span = span;
+            }
+            return Ok(id);
+        }
+
+        let id = self.push(Symbol {
+            name: name.to_string(),
+            kind: SymbolKind::Function { params, defined: is_definition },
+            data_type: return_type,
+            storage: Storage::Global,
+            offset: None,
+            span,
+        });
+        self.scopes[0].names.insert(name.to_string(), id);
+        Ok(id)
+    }
+
+    /**
+    Finds the declaration of `name` visible from the innermost scope
+    */
+    pub fn lookup(&self, name: &str) -> Option<SymbolId> {
+        self.scopes.iter()
+            .rev()
+            .find_map(|scope| scope.names.get(name).copied())
+    }
+
+    pub fn resolve(&self, name: &str, span: Span) -> Result<SymbolId> {
+        self.lookup(name).ok_or_else(|| {
+            Diagnostic::error(format!("Use of undeclared identifier '{}'.", name))
+                .with_code("E0304")
+                .at(span)
+                .into()
+        })
+    }
+
+    pub fn get(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id]
+    }
+
+    pub fn get_mut(&mut self, id: SymbolId) -> &mut Symbol {
+        &mut self.symbols[id]
+    }
+
+    fn push(&mut self, symbol: Symbol) -> SymbolId {
+        self.symbols.push(symbol);
+        self.symbols.len() - 1
+    }
+
+    fn redeclaration(&self, name: &str, previous: SymbolId, span: Span) -> CompilerErrorKind {
+        let previous = &self.symbols[previous];
+        Diagnostic::error(format!("Redeclaration of '{}'.", name))
+            .with_code("E0301")
+            .at(span)
+            .with_secondary(previous.span, format!("Previous declaration of '{}' is here.", name))
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line: usize, column: usize) -> Span {
+        Span::new(0, 0, 0, line, column)
+    }
+
+    #[test]
+    fn test_shadowing() {
+        let mut table = SymbolTable::new();
+        table.enter_scope(ScopeKind::Function);
+        let outer = table.declare_variable("a", DataType::Int, Storage::Local, at(1, 1)).unwrap();
+
+        table.enter_scope(ScopeKind::Block);
+        assert_eq!(table.resolve("a", at(2, 1)).unwrap(), outer);
+        let inner = table.declare_variable("a", DataType::Long, Storage::Local, at(2, 5)).unwrap();
+        assert_eq!(table.lookup("a"), Some(inner));
+        table.leave_scope();
+
+        assert_eq!(table.lookup("a"), Some(outer));
+        assert_eq!(table.get(inner).data_type, DataType::Long);
+    }
+
+    #[test]
+    fn test_redeclaration_and_undeclared() {
+        let mut table = SymbolTable::new();
+        table.enter_scope(ScopeKind::Function);
+        table.declare_variable("a", DataType::Int, Storage::Parameter(0), at(1, 10)).unwrap();
+
+        let diagnostic = Diagnostic::from(table.declare_variable("a", DataType::Int, Storage::Local, at(2, 9)).unwrap_err());
+        assert_eq!(diagnostic.primary_span(), Some(at(2, 9)));
+        assert_eq!(diagnostic.labels[1].span, at(1, 10));
+        let diagnostic = Diagnostic::from(table.resolve("b", at(3, 4)).unwrap_err());
+        assert_eq!((diagnostic.code, diagnostic.primary_span()), (Some("E0304"), Some(at(3, 4))));
+        assert_eq!(diagnostic.message, "Use of undeclared identifier 'b'.");
+    }
+
+    #[test]
+    fn test_function_declarations() {
+        let mut table = SymbolTable::new();
+        let prototype = table.declare_function("f", DataType::Int, vec!(DataType::Int), false, at(1, 5)).unwrap();
+        let definition = table.declare_function("f", DataType::Int, vec!(DataType::Int), true, at(2, 5)).unwrap();
+        assert_eq!(prototype, definition);
+
+        assert!(table.declare_function("f", DataType::Int, vec!(DataType::Int), true, at(3, 5)).is_err());
+        assert!(table.declare_function("f", DataType::Long, vec!(DataType::Int), false, at(4, 5)).is_err());
+    }
+}
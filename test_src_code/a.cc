@@ -1,4 +1,4 @@
-{
+int main() {
 
 
 print 12 * 3;
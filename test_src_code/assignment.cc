@@ -0,0 +1,19 @@
+int main() {
+    int x = 3;
+    long y;
+    int z;
+    y = z = x * 2;
+    print y;
+    print z;
+    x += 3;
+    print x;
+    x -= 1; print x;
+    x *= 4; print x;
+    x /= 3; print x;
+    x &= 6; print x;
+    x |= 9; print x;
+    x ^= 3; print x;
+    y += x = 2;
+    print y;
+    print x;
+}
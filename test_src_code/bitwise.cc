@@ -0,0 +1,17 @@
+int main() {
+    int x = 13;
+    print x % 5;
+    print -13 % 5;
+    print 1 << 4;
+    print -64 >> 2;
+    print x & 6 | 1;
+    print x ^ 5;
+    print 1 | 2 ^ 3 & 4;
+    print 1 << 2 + 1;
+    print x & 1 == 1;
+    print 3 + 4 % 3 * 2;
+    x %= 4; print x;
+    x <<= 3; print x;
+    x >>= 1; print x;
+    print 6 & 3 && 4 | 0;
+}
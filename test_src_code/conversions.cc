@@ -0,0 +1,21 @@
+long big(void) { return 4294967296 + 5; }
+int narrow(long x) { return x; }
+int main() {
+    long l = big();
+    int i = l;
+    print i;
+    print narrow(l + 7);
+    i += l;
+    print i;
+    long m = 3;
+    m <<= 33;
+    print m >> 32;
+    int s = -1;
+    long t = s;
+    print t < 0;
+    i = 2147483647;
+    l = i;
+    l = l + 1;
+    print l > i;
+    return 0;
+}
@@ -0,0 +1,56 @@
+double sqrt(double x);
+
+double average(double a, double b) { return (a + b) / 2; }
+float scale(float x, int factor) { return x * factor; }
+double mixed(int a, double b, int c, double d, int e, double f, int g, double h,
+             int i, double j, int k, double l, int m, double n, double o, double p, int q, double r) {
+    return a + b + c + d + e + f + g + h + i + j + k + l + m + n + o + p + q + r;
+}
+int truncate(double x) { return x; }
+unsigned long to_ulong(double x) { return x; }
+double from_ulong(unsigned long x) { return x; }
+int main() {
+    double d = 3.14;
+    print d;
+    print 1e3 + .5;
+    print 2.5e-1f;
+    float f = 1.1f;
+    print f * 3;
+    print average(1, 2);
+    print scale(2.5f, 3);
+    print mixed(1, 2.5, 3, 4.5, 5, 6.5, 7, 8.5, 9, 10.5, 11, 12.5, 13, 14.5, 15.5, 16.5, 17, 18.5);
+    print truncate(-7.9);
+    print truncate(7.9);
+    print to_ulong(1e19);
+    print from_ulong(to_ulong(1e19) + 12345);
+    print sqrt(2);
+    print 7 / 2 * 1.0;
+    print 7 / 2.0;
+    int i = 10;
+    i += 2.7;
+    print i;
+    d = 0.1;
+    d *= 3;
+    print d == 0.3;
+    print d > 0.3;
+    print d != d;
+    double x = 0.0;
+    print !x;
+    if (x) print 1; else print 0;
+    x -= 1.5;
+    print -x;
+    x++;
+    print x;
+    f = d;
+    print f;
+    print f < d;
+    long l = -d * 1000000;
+    print l;
+    print (f && d) + (x || 0.0);
+    double sum = 0;
+    for (double k = 0.5; k < 3; k += 0.5) {
+        sum += k * sqrt(k);
+    }
+    print sum;
+    print (1.5 + (2.5 * (3.5 - (4.5 / (5.5 + (6.5 * (7.5 - (8.5 + (9.5 * (10.5 - 1.0)))))))))) * 2;
+}
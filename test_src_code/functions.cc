@@ -0,0 +1,26 @@
+int add(int a, int b);
+long sum8(long a, long b, long c, long d, long e, long f, long g, long h) {
+    return a + 2*b + 3*c + 4*d + 5*e + 6*f + 7*g + 8*h;
+}
+int sum7(int a, int b, int c, int d, int e, int f, int g) {
+    return a - b + c - d + e - f + g * 100;
+}
+int fib(int n) {
+    if (n < 2) return n;
+    return fib(n - 1) + fib(n - 2);
+}
+void hello(void) {
+    print 42;
+    return;
+}
+int main() {
+    print add(3, 4);
+    print 1 + add(2, add(3, 4)) * 2;
+    print fib(15);
+    print sum8(1, 2, 3, 4, 5, 6, 7, 8);
+    print sum7(1, 2, 3, 4, 5, 6, 7);
+    hello();
+    print 1 + (2 + (3 + (4 + (5 + (6 + (7 + add(8, 9)))))));
+    return fib(10);
+}
+int add(int a, int b) { return a + b; }
@@ -0,0 +1,18 @@
+int main() {
+    int x = 5;
+    if (x > 3) print 1; else print 0;
+    if (x < 3) print 1; else print 0;
+    if (x == 5) {
+        int y = x * 2;
+        print y;
+        if (y > 100) print 100;
+        else if (y > 5) { print 5; }
+        else print 0;
+    }
+    if (0) print 42;
+    if (x) if (0) print 1; else print 2;
+    if (x) ; else print 3;
+    {
+        { print 7; }
+    }
+}
@@ -0,0 +1,53 @@
+unsigned char uc_add(unsigned char a, unsigned char b) { return a + b; }
+short to_short(int x) { return x; }
+unsigned int umul(unsigned int a, unsigned int b) { return a * b; }
+signed char sc(int x) { return x; }
+int main() {
+    unsigned char b = 250;
+    b += 10;
+    print b;
+    char c = 127;
+    c++;
+    print c;
+    print ++c;
+    unsigned short us = 65535;
+    us++;
+    print us;
+    short s = 32767;
+    s = s + 1;
+    print s;
+    print to_short(70000);
+    int i = 2147483647;
+    i = i + 1;
+    print i;
+    unsigned int u = 0;
+    u = u - 1;
+    print u;
+    print u / 2;
+    print u % 7;
+    print u >> 28;
+    print -1 < u;
+    print umul(65536, 65537);
+    print uc_add(200, 100);
+    long l = -1;
+    unsigned long ul = l;
+    print ul;
+    print ul / 3;
+    print ul >> 60;
+    print l >> 60;
+    long long ll = 9223372036854775807;
+    ll = ll + 1;
+    print ll;
+    unsigned long long ull = 3;
+    print ull - 4 > 0;
+    int neg = -7;
+    print neg / 2;
+    print neg % 3;
+    unsigned int big = 4000000000;
+    print big;
+    print big + big;
+    long promoted = big;
+    print promoted * 2;
+    print sc(200);
+    return 0;
+}
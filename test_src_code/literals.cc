@@ -0,0 +1,15 @@
+int main() {
+    print 0x1F + 017 + 0b101;
+    print 10u - 11;
+    print 0xFFFFFFFF;
+    print 0xFFFFFFFFFFFFFFFF;
+    print 18446744073709551615ULL / 3;
+    print 2147483648;
+    print 4294967295 + 1;
+    print 0x7fffffffffffffffL;
+    print -1 < 0u;
+    print -1L < 0ul;
+    print 1LL << 40;
+    print 0;
+    print 00;
+}
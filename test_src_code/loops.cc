@@ -0,0 +1,29 @@
+int main() {
+    int i = 0;
+    int sum = 0;
+    while (i < 10) {
+        i++;
+        if (i % 2 == 0) continue;
+        sum += i;
+    }
+    print sum;
+    long total = 0;
+    for (int j = 0; j < 5; j++) {
+        int k = 0;
+        for (;;) {
+            if (k >= j) break;
+            total += k;
+            k++;
+        }
+    }
+    print total;
+    int n = 0;
+    do {
+        n += 3;
+        if (n > 100) break;
+    } while (n < 20);
+    print n;
+    do n--; while (0);
+    print n;
+    for (i = 0; i < 3; ++i) print i;
+}
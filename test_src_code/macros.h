@@ -0,0 +1,19 @@
+#ifndef MACROS_H
+#define MACROS_H
+
+#define SQUARE(x) ((x) * (x))
+#define TWICE(x) (2 * (x))
+#define STR(s) #s
+#define XSTR(s) STR(s)
+#define CAT(a, b) a ## b
+#define LIMIT \
+    100
+
+int clamp(int value) {
+    if (value > LIMIT) {
+        return LIMIT;
+    }
+    return value;
+}
+
+#endif
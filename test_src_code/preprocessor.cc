@@ -0,0 +1,45 @@
+#include "macros.h"
+#include "macros.h"
+
+#define N 7
+#define SUM3(a, b, c) ((a) + (b) + (c))
+#define PLUS_N(x) (x + N)
+
+int main() {
+    int self = 1;
+#define self self + 1
+    int CAT(count, 1) = SQUARE(N + 1);
+    print count1;
+    print self;
+    print TWICE(SQUARE(3));
+    print SUM3(1, TWICE(2), (3 - 4));
+    print clamp(SQUARE(11));
+    print clamp(SQUARE(9));
+    print STR(a  +   b);
+    print XSTR(LIMIT);
+    print CAT(1, 2) + CAT(0x, 10);
+#undef N
+#define N 100
+    print PLUS_N(N);
+    print XSTR(CAT(N, 5));
+#if N > 50 && defined(LIMIT)
+    print 1;
+#elif N
+    print 2;
+#else
+#error N must be set
+#endif
+#if defined UNDEFINED || (N / 3 == 33 && -1 < 0)
+    print 3;
+#endif
+#ifdef TWICE
+    print TWICE(21);
+#endif
+#if __STDC__ && __STDC_VERSION__ >= 201112L && defined(__x86_64__) && __linux__
+    print 4;
+#endif
+#line 100
+    print __LINE__;
+    print PLUS_N(__LINE__);
+    return 0;
+}
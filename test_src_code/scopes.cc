@@ -0,0 +1,21 @@
+int x(int a) {
+    int b = a;
+    {
+        int a = 10;
+        b = b + a;
+        {
+            long a = 100;
+            b = b + a;
+        }
+        b = b + a;
+    }
+    for (int i = 0; i < 3; i++) b = b + i;
+    for (int i = 0; i < 2; i++) { int i = 7; b = b + i; }
+    return b + a;
+}
+int main() {
+    print x(1);
+    int v = 5;
+    { int v = 6; print v; }
+    print v;
+}
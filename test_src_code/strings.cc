@@ -0,0 +1,31 @@
+int puts(char *s);
+int printf(char *format, int value);
+int strlen(char *s);
+
+char *greeting(int formal) {
+    if (formal) return "Good " "day";
+    return "hi";
+}
+int main() {
+    print 'A';
+    print '\n' + '\t';
+    print '\x41' == 'A';
+    print '\101';
+    print '\377';
+    print '\'';
+    print "tab\there";
+    print "quote \"inside\" and back\\slash";
+    char *s = "hello";
+    char *t = "hello";
+    print s == t;
+    print greeting(1);
+    print greeting(0);
+    puts("from puts");
+    printf("%d apples\n", 42);
+    print strlen("abc" "def" "\x41\0hidden");
+    char c = 'z';
+    c -= 'a' - 'A';
+    print c;
+    if ("") print "non-null";
+    print "caf\303\251";
+}
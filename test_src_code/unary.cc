@@ -0,0 +1,21 @@
+int main() {
+    int x = 5;
+    print -5;
+    print -x + 2;
+    print +x;
+    print ~x;
+    print - -x;
+    print x++;
+    print x;
+    print ++x;
+    print x--;
+    print --x;
+    print x;
+    print -x++ * 2;
+    print x;
+    long y = 10;
+    y++;
+    ++y;
+    print y;
+    print !-x;
+}
@@ -0,0 +1,8 @@
+int main() {
+    int x = 3 * 4;
+    long y;
+    int a, b = x + 1, c = 7;
+    print x;
+    print b * c - x;
+    print x / 2 + b;
+}